@@ -0,0 +1,153 @@
+//! Pluggable coding-agent transcript sources.
+//!
+//! The watcher used to hardcode Claude Code's layout — the
+//! `~/.claude/projects` root, the `.jsonl`-under-that-dir path test, the
+//! `-`-as-separator project decoding, and the `"claude-code"` source-agent
+//! literal. [`AgentSource`] pulls that knowledge behind a trait so other
+//! agents' transcript formats (a Codex/Aider-style JSONL, a plain
+//! append-only log, ...) can be watched by adding an implementation rather
+//! than editing the core dispatch loop in `watcher`.
+
+use std::path::{Path, PathBuf};
+
+/// Decode a Claude Code project directory name back into an absolute path.
+///
+/// Claude Code does not URL-encode these names: it mangles the absolute
+/// project path by replacing every `/` (and `.`) with `-`, e.g.
+/// `/Users/me/code/myapp` becomes `-Users-me-code-myapp`. Reconstruct the
+/// path by greedily joining tokens with `/`, falling back to `.` when the
+/// `/`-joined candidate doesn't exist on disk — either as a dotted suffix
+/// onto the previous component (to recover a trailing dotted segment like
+/// `app.config`) or as a hidden child directory (to recover a leading-dot
+/// segment like `.config`) — probing the filesystem at each step to resolve
+/// the ambiguity. Shared by `commands::scan_projects` (decoding
+/// `~/.claude/projects` entries) and [`ClaudeCodeSource::decode_project_dir`]
+/// below, so there's only one decoder for this mangling scheme instead of
+/// two that can drift.
+pub(crate) fn decode_claude_project_path(encoded: &str) -> Option<String> {
+    let trimmed = encoded.trim_start_matches('-');
+    let tokens: Vec<&str> = trimmed.split('-').filter(|s| !s.is_empty()).collect();
+    let (first, rest) = tokens.split_first()?;
+
+    let mut path = PathBuf::from("/");
+    path.push(first);
+
+    for token in rest {
+        let as_child = path.join(token);
+        if as_child.exists() {
+            path = as_child;
+            continue;
+        }
+
+        let dotted_name = path
+            .file_name()
+            .map(|name| format!("{}.{}", name.to_string_lossy(), token));
+        if let Some(name) = dotted_name {
+            let mut dotted = path.clone();
+            dotted.set_file_name(name);
+            if dotted.exists() {
+                path = dotted;
+                continue;
+            }
+        }
+
+        let hidden_child = path.join(format!(".{token}"));
+        if hidden_child.exists() {
+            path = hidden_child;
+            continue;
+        }
+
+        // None of the probes resolved to a real path; keep the `/`-joined
+        // guess so later tokens still have something to probe against.
+        path = as_child;
+    }
+
+    path.exists().then(|| path.to_string_lossy().to_string())
+}
+
+/// What a [`AgentSource`] extracted from one transcript line.
+pub struct ParsedPayload {
+    pub payload: Option<String>,
+    /// Links a tool-call event to the tool-result event that completes it.
+    /// See `crate::correlation::ToolCallTracker`.
+    pub tool_use_id: Option<String>,
+}
+
+/// A coding agent whose transcripts the watcher knows how to follow.
+pub trait AgentSource {
+    /// Directories to register with the filesystem debouncer.
+    fn watch_roots(&self) -> Vec<PathBuf>;
+
+    /// Whether `path` is a transcript file belonging to this source.
+    fn matches(&self, path: &Path) -> bool;
+
+    /// The value to stamp on `AgentEvent.source_agent` for events from
+    /// this source.
+    fn source_agent(&self) -> &str;
+
+    /// Recover the on-disk project directory from a transcript path.
+    fn decode_project_dir(&self, path: &Path) -> String;
+
+    /// Parse one transcript line into a `(tool_name, payload)` pair.
+    /// Returns `None` if the line doesn't parse as this source's format at
+    /// all (as opposed to parsing into a recognized-but-uninteresting
+    /// shape, which implementations should still surface as a best-effort
+    /// preview rather than `None`).
+    fn parse_entry(&self, line: &str) -> Option<(String, ParsedPayload)>;
+}
+
+/// Claude Code's `~/.claude/projects/{encoded-project}/{session}.jsonl`
+/// transcripts.
+pub struct ClaudeCodeSource {
+    root: PathBuf,
+}
+
+impl ClaudeCodeSource {
+    pub fn new() -> Option<Self> {
+        let root = dirs::home_dir()?.join(".claude/projects");
+        Some(Self { root })
+    }
+}
+
+impl AgentSource for ClaudeCodeSource {
+    fn watch_roots(&self) -> Vec<PathBuf> {
+        vec![self.root.clone()]
+    }
+
+    fn matches(&self, path: &Path) -> bool {
+        let path_str = path.to_string_lossy();
+        path_str.ends_with(".jsonl") && path_str.contains(".claude/projects")
+    }
+
+    fn source_agent(&self) -> &str {
+        "claude-code"
+    }
+
+    fn decode_project_dir(&self, path: &Path) -> String {
+        // Path format: ~/.claude/projects/{encoded-project}/session.jsonl
+        let encoded_project = path
+            .parent()
+            .and_then(|p| p.file_name())
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        // `decode_claude_project_path` probes the filesystem to recover
+        // dashes/dots that are part of the real path, not separators; fall
+        // back to the raw encoded name if it can't resolve to a real
+        // directory (e.g. the project has since been deleted).
+        decode_claude_project_path(&encoded_project).unwrap_or(encoded_project)
+    }
+
+    fn parse_entry(&self, line: &str) -> Option<(String, ParsedPayload)> {
+        let entry = serde_json::from_str::<crate::transcript::TranscriptEntry>(line).ok()?;
+        let tool_use_id = crate::transcript::tool_use_id(&entry);
+        let (tool_name, payload) = crate::transcript::summarize(entry);
+        Some((
+            tool_name?,
+            ParsedPayload {
+                payload,
+                tool_use_id,
+            },
+        ))
+    }
+}
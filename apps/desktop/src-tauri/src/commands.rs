@@ -1,13 +1,38 @@
+use crate::agent_source::decode_claude_project_path;
 use crate::db::{Config, DbState, Feature, AgentEvent, Session, Stats};
+use crate::jobs::{Job, JobKind, JobManagerState};
+use crate::watcher::WatcherState;
+use std::path::Path;
 use tauri::State;
 
 #[tauri::command]
 pub async fn get_features(
     db: State<'_, DbState>,
     project_dir: Option<String>,
+    with_git_status: Option<bool>,
 ) -> Result<Vec<Feature>, String> {
-    db.0.get_features(project_dir.as_deref())
-        .map_err(|e| e.to_string())
+    let mut features = db
+        .0
+        .get_features(project_dir.as_deref())
+        .map_err(|e| e.to_string())?;
+
+    if with_git_status.unwrap_or(false) {
+        for feature in &mut features {
+            feature.git_status = db.0.cached_git_status(&feature.project_dir);
+        }
+    }
+
+    Ok(features)
+}
+
+#[tauri::command]
+pub async fn get_project_git_status(
+    db: State<'_, DbState>,
+    project_dir: String,
+) -> Result<crate::db::GitStatus, String> {
+    let status = crate::git_status::compute_git_status(&project_dir).map_err(|e| e.to_string())?;
+    db.0.set_git_status(&project_dir, status.clone());
+    Ok(status)
 }
 
 #[tauri::command]
@@ -40,76 +65,190 @@ pub async fn get_stats(db: State<'_, DbState>) -> Result<Stats, String> {
 }
 
 #[tauri::command]
-pub async fn scan_projects() -> Result<Vec<String>, String> {
-    let home = dirs::home_dir().ok_or("No home directory")?;
-    let mut projects = vec![];
-
-    // Common project locations
-    let search_dirs = vec![
-        home.join("projects"),
-        home.join("code"),
-        home.join("dev"),
-        home.join("workspace"),
-        home.join("Documents/projects"),
-    ];
-
-    for search_dir in search_dirs {
-        if !search_dir.exists() {
-            continue;
+pub async fn search(
+    db: State<'_, DbState>,
+    query: String,
+    limit: Option<i64>,
+) -> Result<Vec<crate::search::SearchHit>, String> {
+    crate::search::search(&db.0, &query, limit.unwrap_or(20)).map_err(|e| e.to_string())
+}
+
+/// Semantic search over previously-indexed transcript content, e.g. "where
+/// did the agent edit the auth middleware last week".
+#[tauri::command]
+pub async fn search_sessions(
+    embeddings: State<'_, crate::embeddings::EmbeddingState>,
+    query: String,
+    top_k: Option<usize>,
+) -> Result<Vec<crate::embeddings::SessionSearchHit>, String> {
+    embeddings
+        .index
+        .search_sessions(embeddings.backend.as_ref(), &query, top_k.unwrap_or(10))
+        .map_err(|e| e.to_string())
+}
+
+/// Built-in search roots used when `Config::search_roots` is empty.
+fn default_search_roots() -> Vec<String> {
+    let Some(home) = dirs::home_dir() else {
+        return vec![];
+    };
+
+    ["projects", "code", "dev", "workspace", "Documents/projects"]
+        .iter()
+        .map(|sub| home.join(sub).to_string_lossy().to_string())
+        .collect()
+}
+
+/// Resumable state for an in-flight `scan_projects` job: the roots walked so
+/// far and what they turned up. Persisted to the `jobs` table after every
+/// root so a restart mid-scan continues from `next_root_index` instead of
+/// starting over.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct ScanProgress {
+    discovered: Vec<crate::discovery::DiscoveredProject>,
+    next_root_index: usize,
+}
+
+#[tauri::command]
+pub async fn scan_projects(
+    db: State<'_, DbState>,
+    jobs: State<'_, JobManagerState>,
+) -> Result<Vec<crate::discovery::DiscoveredProject>, String> {
+    use crate::discovery::{DiscoveredProject, ProjectManifest};
+
+    let config = db.0.get_config().map_err(|e| e.to_string())?;
+    let roots = if config.search_roots.is_empty() {
+        default_search_roots()
+    } else {
+        config.search_roots
+    };
+
+    // Resume an existing pending scan job if one is still in flight (e.g.
+    // left over from a restart), otherwise start fresh. Any pending
+    // `ProjectScan` job is resumed; its roots are not compared with `roots`.
+    let existing = jobs
+        .0
+        .get_jobs()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|j| j.kind == JobKind::ProjectScan && j.status == crate::jobs::JobStatus::Pending);
+
+    let (job_id, mut progress) = match existing {
+        Some(job) => {
+            let progress = jobs
+                .0
+                .load_payload::<ScanProgress>(job.id)
+                .map_err(|e| e.to_string())?
+                .unwrap_or_default();
+            (job.id, progress)
         }
+        None => {
+            let progress = ScanProgress::default();
+            let id = jobs
+                .0
+                .enqueue(JobKind::ProjectScan, "*", &progress)
+                .map_err(|e| e.to_string())?;
+            (id, progress)
+        }
+    };
 
-        // Look for feature_list.json files
-        let pattern = format!("{}/**/feature_list.json", search_dir.display());
-        if let Ok(paths) = glob::glob(&pattern) {
-            for entry in paths.flatten() {
-                if let Some(parent) = entry.parent() {
-                    projects.push(parent.to_string_lossy().to_string());
-                }
+    jobs.0.mark_running(job_id).map_err(|e| e.to_string())?;
+
+    for (i, root) in roots.iter().enumerate().skip(progress.next_root_index) {
+        if jobs.0.is_cancelled(job_id).unwrap_or(false) {
+            return Ok(progress.discovered);
+        }
+
+        let found = crate::discovery::discover_projects(std::slice::from_ref(root));
+        for project in found {
+            if !progress.discovered.iter().any(|p| p.path == project.path) {
+                progress.discovered.push(project);
             }
         }
+
+        progress.next_root_index = i + 1;
+        let _ = jobs.0.update_progress(job_id, progress.next_root_index as i64, &progress);
     }
 
+    let mut projects = progress.discovered;
+
     // Also check Claude projects directory for recent projects
+    let home = dirs::home_dir().ok_or("No home directory")?;
     let claude_projects = home.join(".claude/projects");
     if claude_projects.exists() {
         if let Ok(entries) = std::fs::read_dir(&claude_projects) {
             for entry in entries.flatten() {
-                // Claude encodes project paths - we'd need to decode them
-                // For now, just note that there are Claude projects
                 let name = entry.file_name().to_string_lossy().to_string();
-                if !name.starts_with('.') {
-                    // Decode the project path (it's typically URL-encoded or similar)
-                    // This is a simplified version
-                    if let Ok(decoded) = urlencoding::decode(&name) {
-                        let path = decoded.to_string();
-                        if std::path::Path::new(&path).exists() && !projects.contains(&path) {
-                            projects.push(path);
-                        }
+                if name.starts_with('.') {
+                    continue;
+                }
+                if let Some(path) = decode_claude_project_path(&name) {
+                    if !projects.iter().any(|p| p.path == path) {
+                        projects.push(DiscoveredProject {
+                            path,
+                            marker_kind: ProjectManifest::ClaudeSession,
+                            last_modified: None,
+                        });
                     }
                 }
             }
         }
     }
 
-    projects.sort();
-    projects.dedup();
+    projects.sort_by(|a, b| a.path.cmp(&b.path));
+    projects.dedup_by(|a, b| a.path == b.path);
+
+    jobs.0.mark_completed(job_id).map_err(|e| e.to_string())?;
 
     Ok(projects)
 }
 
+#[tauri::command]
+pub async fn get_jobs(jobs: State<'_, JobManagerState>) -> Result<Vec<Job>, String> {
+    jobs.0.get_jobs().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn cancel_job(jobs: State<'_, JobManagerState>, job_id: i64) -> Result<(), String> {
+    jobs.0.cancel_job(job_id).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn watch_project(
     db: State<'_, DbState>,
+    watcher: State<'_, WatcherState>,
     project_dir: String,
 ) -> Result<(), String> {
     let mut config = db.0.get_config().map_err(|e| e.to_string())?;
 
     if !config.watched_projects.contains(&project_dir) {
-        config.watched_projects.push(project_dir);
+        config.watched_projects.push(project_dir.clone());
+        db.0.save_config(&config).map_err(|e| e.to_string())?;
+    }
+
+    watcher
+        .0
+        .watch_project(Path::new(&project_dir))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn unwatch_project(
+    db: State<'_, DbState>,
+    watcher: State<'_, WatcherState>,
+    project_dir: String,
+) -> Result<(), String> {
+    let mut config = db.0.get_config().map_err(|e| e.to_string())?;
+
+    if config.watched_projects.iter().any(|p| p == &project_dir) {
+        config.watched_projects.retain(|p| p != &project_dir);
         db.0.save_config(&config).map_err(|e| e.to_string())?;
     }
 
-    Ok(())
+    watcher
+        .0
+        .unwatch_project(Path::new(&project_dir))
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
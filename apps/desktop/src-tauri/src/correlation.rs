@@ -0,0 +1,61 @@
+//! In-memory correlation of tool calls with their results.
+//!
+//! A transcript's `tool_use_id` already links a `ToolUse` block to the
+//! `ToolResult` that answers it, but each line is parsed and stored as its
+//! own independent `AgentEvent` — the frontend would otherwise have to
+//! re-join a flat log of `tool_use`/`tool_result` entries itself to show
+//! "Bash command X produced output Y". [`ToolCallTracker`] caches the
+//! originating call's event id per `(session_id, tool_use_id)` while it's
+//! outstanding, so when the matching result arrives the watcher can
+//! annotate that original row and emit a `ToolCallCompleted` follow-up
+//! instead.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+struct PendingCall {
+    event_id: i64,
+    tool_name: String,
+    started_at: Instant,
+}
+
+#[derive(Default)]
+pub struct ToolCallTracker {
+    pending: Mutex<HashMap<(String, String), PendingCall>>,
+}
+
+impl ToolCallTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a newly-seen tool call so a later result in the same session
+    /// with the same `tool_use_id` can be joined back to it.
+    pub fn record_call(&self, session_id: &str, tool_use_id: &str, event_id: i64, tool_name: &str) {
+        self.pending.lock().unwrap().insert(
+            (session_id.to_string(), tool_use_id.to_string()),
+            PendingCall {
+                event_id,
+                tool_name: tool_name.to_string(),
+                started_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Resolve a result's `tool_use_id` against an outstanding call in the
+    /// same session, removing it from the pending set. Returns the
+    /// originating event id, its tool name, and how long the call was
+    /// outstanding, so the caller can annotate that event and emit a
+    /// `ToolCallCompleted` event. Returns `None` for a result with no
+    /// matching call (e.g. it arrived before the watcher started, or the
+    /// call's line was never parsed).
+    pub fn resolve(&self, session_id: &str, tool_use_id: &str) -> Option<(i64, String, Duration)> {
+        let pending = self
+            .pending
+            .lock()
+            .unwrap()
+            .remove(&(session_id.to_string(), tool_use_id.to_string()))?;
+        Some((pending.event_id, pending.tool_name, pending.started_at.elapsed()))
+    }
+}
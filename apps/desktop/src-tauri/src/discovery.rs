@@ -0,0 +1,101 @@
+//! Recursive project discovery.
+//!
+//! Walks a configurable set of search roots looking for directories that
+//! look like projects, skipping `.gitignore`d paths and well-known heavy
+//! directories (`node_modules`, `target`, `.git`) along the way.
+
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Which manifest marker identified a directory as a project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProjectManifest {
+    FeatureList,
+    Cargo,
+    Npm,
+    Git,
+    /// Discovered via `~/.claude/projects` rather than a manifest marker.
+    ClaudeSession,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoveredProject {
+    pub path: String,
+    pub marker_kind: ProjectManifest,
+    pub last_modified: Option<String>,
+}
+
+/// Directories we never want to descend into, regardless of `.gitignore`.
+const SKIP_DIRS: &[&str] = &["node_modules", "target", ".git"];
+
+fn detect_marker(dir: &Path) -> Option<ProjectManifest> {
+    if dir.join("feature_list.json").exists() {
+        Some(ProjectManifest::FeatureList)
+    } else if dir.join("Cargo.toml").exists() {
+        Some(ProjectManifest::Cargo)
+    } else if dir.join("package.json").exists() {
+        Some(ProjectManifest::Npm)
+    } else if dir.join(".git").exists() {
+        Some(ProjectManifest::Git)
+    } else {
+        None
+    }
+}
+
+fn last_modified(dir: &Path) -> Option<String> {
+    let modified: SystemTime = fs::metadata(dir).ok()?.modified().ok()?;
+    Some(chrono::DateTime::<chrono::Utc>::from(modified).to_rfc3339())
+}
+
+/// Recursively discover projects under `roots`.
+///
+/// Each root is walked with the `ignore` crate so `.gitignore` rules apply,
+/// with `node_modules`/`target`/`.git` force-excluded from traversal on top
+/// of that. A directory is reported as a project the first time it matches
+/// one of `feature_list.json`, `Cargo.toml`, `package.json`, or `.git`, in
+/// that priority order.
+pub fn discover_projects(roots: &[String]) -> Vec<DiscoveredProject> {
+    let mut projects = Vec::new();
+
+    for root in roots {
+        let root_path = Path::new(root);
+        if !root_path.exists() {
+            continue;
+        }
+
+        let mut overrides = OverrideBuilder::new(root_path);
+        for dir in SKIP_DIRS {
+            let _ = overrides.add(&format!("!{}", dir));
+        }
+        let overrides = match overrides.build() {
+            Ok(o) => o,
+            Err(_) => continue,
+        };
+
+        let walker = WalkBuilder::new(root_path).overrides(overrides).build();
+
+        for entry in walker.flatten() {
+            if !entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                continue;
+            }
+
+            if let Some(marker_kind) = detect_marker(entry.path()) {
+                projects.push(DiscoveredProject {
+                    path: entry.path().to_string_lossy().to_string(),
+                    marker_kind,
+                    last_modified: last_modified(entry.path()),
+                });
+            }
+        }
+    }
+
+    projects.sort_by(|a, b| a.path.cmp(&b.path));
+    projects.dedup_by(|a, b| a.path == b.path);
+    projects
+}
@@ -0,0 +1,302 @@
+//! Semantic search over parsed transcript content.
+//!
+//! As the watcher parses transcript lines it hands the resulting preview
+//! text to an [`EmbeddingQueue`], which chunks and embeds it on its own
+//! background thread — never the watcher thread, so embedding latency never
+//! delays the next file-change event — and stores the vectors in a local
+//! index keyed by `(project_dir, session_id, feature_id)`. `search_sessions`
+//! answers a query by embedding it with the same [`EmbeddingBackend`] and
+//! ranking indexed chunks by cosine similarity, the same brute-force
+//! linear-scan tradeoff `rocksdb_backend`'s key-suffix scans make: the
+//! corpus here is one user's transcript history, not a shared corpus, so an
+//! ANN index would be solving a problem this scale doesn't have yet.
+
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+
+/// Fixed dimensionality of every stored vector, so `EmbeddingIndex::search_sessions`
+/// can compare rows without storing their length alongside them.
+pub const EMBEDDING_DIM: usize = 64;
+
+/// Turns text into a fixed-size embedding vector. Kept as a trait so a real
+/// local model or a remote embedding endpoint can be swapped in without
+/// touching the indexing or search plumbing — the same "one trait, multiple
+/// backends" shape `storage::Storage` uses for sqlite/rocksdb.
+pub trait EmbeddingBackend: Send + Sync {
+    fn embed(&self, text: &str) -> Vec<f32>;
+}
+
+/// Deterministic placeholder backend: hashes each lowercased token into one
+/// of `EMBEDDING_DIM` buckets and L2-normalizes the result. No model weights
+/// or network calls, so indexing and search work out of the box; swap in a
+/// real `EmbeddingBackend` (local model or remote endpoint) once one is
+/// wired up.
+pub struct HashingEmbeddingBackend;
+
+impl EmbeddingBackend for HashingEmbeddingBackend {
+    fn embed(&self, text: &str) -> Vec<f32> {
+        let mut buckets = vec![0f32; EMBEDDING_DIM];
+
+        for token in text.split_whitespace() {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            token.to_lowercase().hash(&mut hasher);
+            buckets[(hasher.finish() as usize) % EMBEDDING_DIM] += 1.0;
+        }
+
+        let norm = buckets.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            for bucket in &mut buckets {
+                *bucket /= norm;
+            }
+        }
+        buckets
+    }
+}
+
+/// Split `text` into roughly `max_chars`-sized chunks, breaking on
+/// whitespace so a chunk never splits a word. Keeps each embedding scoped to
+/// a coherent span instead of diluting one vector over an entire (possibly
+/// long) tool payload.
+pub fn chunk_text(text: &str, max_chars: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.len() + 1 + word.len() > max_chars {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+
+    chunks
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSearchHit {
+    pub project_dir: String,
+    pub session_id: String,
+    pub feature_id: Option<String>,
+    pub event_id: Option<i64>,
+    pub chunk_text: String,
+    pub score: f32,
+}
+
+/// Local vector index over embedded transcript chunks, keyed by
+/// `(project_dir, session_id, feature_id)`. Owns its own sqlite connection
+/// to the app's database file rather than going through the `Storage` trait
+/// — the same precedent `jobs::JobManager` sets for state that isn't part of
+/// the core events/features/sessions schema.
+pub struct EmbeddingIndex {
+    conn: Mutex<Connection>,
+}
+
+impl EmbeddingIndex {
+    pub fn new(path: &Path) -> Result<Self, rusqlite::Error> {
+        let conn = Connection::open(path)?;
+
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS session_embeddings (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                project_dir TEXT NOT NULL,
+                session_id TEXT NOT NULL,
+                feature_id TEXT,
+                event_id INTEGER,
+                chunk_text TEXT NOT NULL,
+                vector BLOB NOT NULL,
+                created_at TEXT DEFAULT (datetime('now'))
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_session_embeddings_project ON session_embeddings(project_dir);
+            CREATE INDEX IF NOT EXISTS idx_session_embeddings_session ON session_embeddings(session_id);
+            "#,
+        )?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn insert(
+        &self,
+        project_dir: &str,
+        session_id: &str,
+        feature_id: Option<&str>,
+        event_id: Option<i64>,
+        chunk_text: &str,
+        vector: &[f32],
+    ) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO session_embeddings (project_dir, session_id, feature_id, event_id, chunk_text, vector)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                project_dir,
+                session_id,
+                feature_id,
+                event_id,
+                chunk_text,
+                encode_vector(vector),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Embed `query` with `backend` and return the `top_k` indexed chunks
+    /// ranked by descending cosine similarity.
+    pub fn search_sessions(
+        &self,
+        backend: &dyn EmbeddingBackend,
+        query: &str,
+        top_k: usize,
+    ) -> Result<Vec<SessionSearchHit>, rusqlite::Error> {
+        let query_vector = backend.embed(query);
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT project_dir, session_id, feature_id, event_id, chunk_text, vector FROM session_embeddings",
+        )?;
+
+        let mut hits = stmt
+            .query_map([], |row| {
+                let vector_bytes: Vec<u8> = row.get(5)?;
+                Ok(SessionSearchHit {
+                    project_dir: row.get(0)?,
+                    session_id: row.get(1)?,
+                    feature_id: row.get(2)?,
+                    event_id: row.get(3)?,
+                    chunk_text: row.get(4)?,
+                    score: cosine_similarity(&query_vector, &decode_vector(&vector_bytes)),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        hits.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        hits.truncate(top_k);
+        Ok(hits)
+    }
+}
+
+fn encode_vector(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|f| f.to_le_bytes()).collect()
+}
+
+fn decode_vector(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect()
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+/// Tauri-managed handle to the index and backend, so commands (e.g.
+/// `search_sessions`) can query them without needing the background queue.
+pub struct EmbeddingState {
+    pub index: Arc<EmbeddingIndex>,
+    pub backend: Arc<dyn EmbeddingBackend>,
+}
+
+struct EmbedTask {
+    project_dir: String,
+    session_id: String,
+    feature_id: Option<String>,
+    event_id: Option<i64>,
+    text: String,
+}
+
+/// Background worker that chunks, embeds, and indexes transcript text off
+/// the watcher thread. `enqueue` never blocks: it only pushes onto an
+/// in-memory channel, so a burst of transcript lines is never held up
+/// waiting on embedding latency.
+pub struct EmbeddingQueue {
+    tx: Sender<EmbedTask>,
+}
+
+impl EmbeddingQueue {
+    pub fn start(index: Arc<EmbeddingIndex>, backend: Arc<dyn EmbeddingBackend>) -> Self {
+        let (tx, rx) = std::sync::mpsc::channel::<EmbedTask>();
+
+        std::thread::spawn(move || {
+            for task in rx {
+                for chunk in chunk_text(&task.text, 500) {
+                    let vector = backend.embed(&chunk);
+                    if let Err(e) = index.insert(
+                        &task.project_dir,
+                        &task.session_id,
+                        task.feature_id.as_deref(),
+                        task.event_id,
+                        &chunk,
+                        &vector,
+                    ) {
+                        tracing::error!("Failed to index session embedding: {}", e);
+                    }
+                }
+            }
+        });
+
+        Self { tx }
+    }
+
+    /// Queue `text` for background chunking/embedding/indexing under
+    /// `(project_dir, session_id, feature_id)`, linked back to `event_id`.
+    pub fn enqueue(
+        &self,
+        project_dir: &str,
+        session_id: &str,
+        feature_id: Option<&str>,
+        event_id: Option<i64>,
+        text: &str,
+    ) {
+        let _ = self.tx.send(EmbedTask {
+            project_dir: project_dir.to_string(),
+            session_id: session_id.to_string(),
+            feature_id: feature_id.map(String::from),
+            event_id,
+            text: text.to_string(),
+        });
+    }
+}
+
+/// Pull the embeddable text (tool input previews, message previews, ...) out
+/// of an `AgentEvent`'s `payload` JSON, skipping structural fields like
+/// `messageType`/`isError`. Returns `None` if the payload has no string
+/// content worth indexing.
+pub fn embeddable_text(payload_json: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(payload_json).ok()?;
+    let object = value.as_object()?;
+
+    let parts: Vec<String> = object
+        .iter()
+        .filter(|(key, _)| key.as_str() != "messageType" && key.as_str() != "isError")
+        .filter_map(|(_, v)| v.as_str())
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect();
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" "))
+    }
+}
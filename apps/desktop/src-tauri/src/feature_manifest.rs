@@ -0,0 +1,75 @@
+//! Locates and parses a project's feature manifest, whichever of
+//! `feature_list.{json,toml,yaml,yml}` the project's agent tooling happens to
+//! emit. Every format is normalized to the same `Vec<serde_json::Value>`
+//! shape `watcher::sync_feature_list`'s field mapping (`description`,
+//! `category`, `passes`, `inProgress`, `agent`, `steps`) already expects, so
+//! that mapping stays format-agnostic.
+
+use std::path::{Path, PathBuf};
+
+/// Manifest formats this app understands, tried in this order when more
+/// than one is present in the same project directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ManifestFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+const CANDIDATES: &[(&str, ManifestFormat)] = &[
+    ("feature_list.json", ManifestFormat::Json),
+    ("feature_list.toml", ManifestFormat::Toml),
+    ("feature_list.yaml", ManifestFormat::Yaml),
+    ("feature_list.yml", ManifestFormat::Yaml),
+];
+
+fn format_for(path: &Path) -> Option<ManifestFormat> {
+    CANDIDATES
+        .iter()
+        .find(|(name, _)| path.file_name().map(|n| n == *name).unwrap_or(false))
+        .map(|(_, format)| *format)
+        .or_else(|| match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Some(ManifestFormat::Json),
+            Some("toml") => Some(ManifestFormat::Toml),
+            Some("yaml") | Some("yml") => Some(ManifestFormat::Yaml),
+            _ => None,
+        })
+}
+
+/// True if `path`'s file name is one this module knows how to parse as a
+/// feature manifest, regardless of format.
+pub fn is_manifest_path(path: &Path) -> bool {
+    CANDIDATES
+        .iter()
+        .any(|(name, _)| path.file_name().map(|n| n == *name).unwrap_or(false))
+}
+
+/// The first `feature_list.*` manifest found directly under `project_dir`,
+/// in `CANDIDATES` order (so `.json` wins if more than one is present).
+pub fn find(project_dir: &Path) -> Option<PathBuf> {
+    CANDIDATES
+        .iter()
+        .map(|(name, _)| project_dir.join(name))
+        .find(|path| path.exists())
+}
+
+/// TOML has no bare top-level array, so a TOML manifest wraps its entries in
+/// an array-of-tables: `[[features]] description = "..." ...`.
+#[derive(serde::Deserialize)]
+struct TomlManifest {
+    #[serde(default)]
+    features: Vec<serde_json::Value>,
+}
+
+/// Parse a feature manifest's contents into the generic
+/// `Vec<serde_json::Value>` shape every format normalizes to, dispatching on
+/// `path`'s file name for the four `CANDIDATES` (falling back to its
+/// extension for any other name).
+pub fn parse(path: &Path, content: &str) -> Result<Vec<serde_json::Value>, Box<dyn std::error::Error>> {
+    match format_for(path) {
+        Some(ManifestFormat::Json) => Ok(serde_json::from_str(content)?),
+        Some(ManifestFormat::Toml) => Ok(toml::from_str::<TomlManifest>(content)?.features),
+        Some(ManifestFormat::Yaml) => Ok(serde_yaml::from_str(content)?),
+        None => Err(format!("unrecognized feature manifest: {}", path.display()).into()),
+    }
+}
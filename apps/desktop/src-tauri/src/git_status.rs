@@ -0,0 +1,40 @@
+//! Per-project git status, backed by `git2`.
+
+use crate::db::GitStatus;
+
+/// Inspect the repository at `project_dir` and report its current branch,
+/// whether the working tree is dirty, and how far it has diverged from its
+/// upstream branch.
+pub fn compute_git_status(project_dir: &str) -> Result<GitStatus, git2::Error> {
+    let repo = git2::Repository::open(project_dir)?;
+
+    let head = repo.head().ok();
+    let branch = head.as_ref().and_then(|h| h.shorthand()).map(String::from);
+
+    let mut opts = git2::StatusOptions::new();
+    opts.include_untracked(true);
+    let statuses = repo.statuses(Some(&mut opts))?;
+    let dirty = !statuses.is_empty();
+
+    let (ahead, behind) = branch
+        .as_deref()
+        .and_then(|name| ahead_behind(&repo, name))
+        .unwrap_or((0, 0));
+
+    Ok(GitStatus {
+        branch,
+        dirty,
+        ahead: ahead as i64,
+        behind: behind as i64,
+    })
+}
+
+fn ahead_behind(repo: &git2::Repository, branch_name: &str) -> Option<(usize, usize)> {
+    let local = repo.find_branch(branch_name, git2::BranchType::Local).ok()?;
+    let local_oid = local.get().target()?;
+
+    let upstream = local.upstream().ok()?;
+    let upstream_oid = upstream.get().target()?;
+
+    repo.graph_ahead_behind(local_oid, upstream_oid).ok()
+}
@@ -4,15 +4,36 @@
 //! SQLite remains as a local read cache for fast UI rendering.
 
 use anyhow::{Context, Result};
-use neo4rs::{query, ConfigBuilder, Graph, Node};
+use async_trait::async_trait;
+use neo4rs::{query, ConfigBuilder, Graph, Node, Query};
+use opentelemetry::metrics::{Counter, Histogram};
+use opentelemetry::KeyValue;
+use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
+use tracing::Instrument;
+
+/// Starting backoff delay for reconnect attempts.
+const RECONNECT_INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+/// How often to run the `RETURN 1` liveness probe while connected.
+const LIVENESS_PROBE_INTERVAL: Duration = Duration::from_secs(10);
 
 /// Graph database connection pool
-pub struct GraphDb {
+pub struct MemgraphStore {
     graph: Arc<RwLock<Option<Graph>>>,
     config: GraphDbConfig,
+    connected: Arc<AtomicBool>,
+    wal: Arc<WriteAheadQueue>,
+    /// In-flight `GraphStore` operations, reported as the `graph.pool.occupancy`
+    /// gauge so operators can see how close they are to `pool_max_size`.
+    active_queries: Arc<AtomicI64>,
+    /// `None` when `GraphDbConfig::otlp_endpoint` is unset, so instrumented
+    /// operations pay only the cost of a branch.
+    telemetry: Option<GraphTelemetry>,
 }
 
 #[derive(Clone, Debug)]
@@ -21,6 +42,24 @@ pub struct GraphDbConfig {
     pub user: String,
     pub password: String,
     pub database: String,
+    /// Where to persist the write-ahead queue used while disconnected.
+    pub wal_path: String,
+    /// Ceiling for the reconnect backoff delay (doubled after every failed
+    /// attempt, capped here).
+    pub reconnect_max_backoff: Duration,
+    /// OTLP collector endpoint (e.g. `http://localhost:4317`) for query
+    /// latency/failure metrics and connection/queue-depth gauges. Telemetry
+    /// is entirely disabled when unset, so there's no overhead in the
+    /// common case of running without a collector.
+    pub otlp_endpoint: Option<String>,
+    /// Ceiling on concurrent Bolt connections the driver may open. Recycling
+    /// of idle connections below that ceiling is handled by the driver's own
+    /// pool.
+    pub pool_max_size: usize,
+    /// Where the embedded SQLite store lives, whether used as a read cache
+    /// in front of Memgraph (`GraphBackend::Layered`) or as the entire
+    /// store (`GraphBackend::Local`).
+    pub sqlite_path: String,
 }
 
 impl Default for GraphDbConfig {
@@ -33,24 +72,583 @@ impl Default for GraphDbConfig {
                 .unwrap_or_else(|_| "".to_string()),
             database: std::env::var("IJOKA_GRAPH_DATABASE")
                 .unwrap_or_else(|_| "memgraph".to_string()),
+            wal_path: std::env::var("IJOKA_GRAPH_WAL_PATH")
+                .unwrap_or_else(|_| "ijoka_graph_wal.db".to_string()),
+            reconnect_max_backoff: std::env::var("IJOKA_GRAPH_RECONNECT_TIMEOUT")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(Duration::from_secs(30)),
+            otlp_endpoint: std::env::var("IJOKA_GRAPH_OTLP_ENDPOINT")
+                .ok()
+                .filter(|s| !s.is_empty()),
+            pool_max_size: std::env::var("IJOKA_GRAPH_POOL_MAX_SIZE")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(10),
+            sqlite_path: std::env::var("IJOKA_GRAPH_SQLITE_PATH")
+                .unwrap_or_else(|_| "ijoka_graph_cache.db".to_string()),
+        }
+    }
+}
+
+/// A single bound Cypher parameter value, restricted to the value shapes
+/// this module actually binds. Kept as a concrete enum (rather than
+/// `serde_json::Value`) so replaying a queued write rebuilds a `Query` the
+/// same way the original call site did.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+enum WalValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+    List(Vec<String>),
+    /// A list of parameter maps, for `UNWIND $param AS x` batch statements.
+    /// Each inner vec is one map, in the same key/value shape as the params
+    /// passed to `execute_mutation` itself.
+    Maps(Vec<Vec<(String, WalValue)>>),
+}
+
+/// A durable write-ahead queue of mutating Cypher statements, used while
+/// the graph connection is down so no writes are silently dropped.
+struct WriteAheadQueue {
+    conn: Mutex<Connection>,
+}
+
+impl WriteAheadQueue {
+    fn open(path: &str) -> Result<Self> {
+        let conn = Connection::open(path).context("Failed to open write-ahead queue")?;
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS wal_queue (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                cypher TEXT NOT NULL,
+                params TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'pending',
+                created_at TEXT DEFAULT (datetime('now'))
+            );
+            "#,
+        )?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn enqueue(&self, cypher: &str, bound_params: &[(String, WalValue)]) -> Result<()> {
+        let params_json = serde_json::to_string(bound_params)?;
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO wal_queue (cypher, params, status) VALUES (?1, ?2, 'pending')",
+            params![cypher, params_json],
+        )?;
+        Ok(())
+    }
+
+    /// Pending rows in sequence order, oldest first.
+    fn pending(&self) -> Result<Vec<(i64, String, Vec<(String, WalValue)>)>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT seq, cypher, params FROM wal_queue WHERE status = 'pending' ORDER BY seq ASC",
+        )?;
+
+        let rows = stmt
+            .query_map([], |row| {
+                let seq: i64 = row.get(0)?;
+                let cypher: String = row.get(1)?;
+                let params_json: String = row.get(2)?;
+                Ok((seq, cypher, params_json))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        rows.into_iter()
+            .map(|(seq, cypher, params_json)| {
+                let bound: Vec<(String, WalValue)> = serde_json::from_str(&params_json)?;
+                Ok((seq, cypher, bound))
+            })
+            .collect()
+    }
+
+    fn mark_applied(&self, seq: i64) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE wal_queue SET status = 'applied' WHERE seq = ?1",
+            params![seq],
+        )?;
+        Ok(())
+    }
+
+    /// Number of statements still waiting to be replayed.
+    fn depth(&self) -> Result<i64> {
+        let conn = self.conn.lock().unwrap();
+        let depth: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM wal_queue WHERE status = 'pending'",
+            [],
+            |r| r.get(0),
+        )?;
+        Ok(depth)
+    }
+}
+
+fn rebuild_query(cypher: &str, bound_params: &[(String, WalValue)]) -> Query {
+    let mut q = query(cypher);
+    for (key, value) in bound_params {
+        q = match value.clone() {
+            WalValue::Bool(v) => q.param(key.as_str(), v),
+            WalValue::Int(v) => q.param(key.as_str(), v),
+            WalValue::Float(v) => q.param(key.as_str(), v),
+            WalValue::Text(v) => q.param(key.as_str(), v),
+            WalValue::List(v) => q.param(key.as_str(), v),
+            WalValue::Maps(maps) => q.param(key.as_str(), wal_maps_to_bolt(maps)),
+        };
+    }
+    q
+}
+
+/// Turn a `WalValue::Maps` payload into the `BoltType::List<BoltType::Map>`
+/// shape `UNWIND $param AS x` expects, so a whole batch of rows can be bound
+/// as a single parameter instead of one round trip per row.
+fn wal_maps_to_bolt(maps: Vec<Vec<(String, WalValue)>>) -> neo4rs::BoltType {
+    let rows = maps
+        .into_iter()
+        .map(|entries| {
+            let mut map = neo4rs::BoltMap::new();
+            for (key, value) in entries {
+                map.put(key.into(), wal_value_to_bolt(value));
+            }
+            neo4rs::BoltType::Map(map)
+        })
+        .collect::<Vec<_>>();
+
+    neo4rs::BoltType::List(neo4rs::BoltList::from(rows))
+}
+
+fn wal_value_to_bolt(value: WalValue) -> neo4rs::BoltType {
+    match value {
+        WalValue::Bool(v) => v.into(),
+        WalValue::Int(v) => v.into(),
+        WalValue::Float(v) => v.into(),
+        WalValue::Text(v) => v.into(),
+        WalValue::List(v) => v.into(),
+        WalValue::Maps(v) => wal_maps_to_bolt(v),
+    }
+}
+
+/// Shared by every `GraphStore::query_events` implementation: `events` was
+/// fetched with one extra lookahead row past `limit`, so its presence means
+/// there's a further page. Trims it off and turns the new last row into the
+/// cursor for that page.
+fn next_cursor(events: &mut Vec<Event>, limit: i64) -> Option<EventCursor> {
+    if events.len() <= limit as usize {
+        return None;
+    }
+    events.truncate(limit as usize);
+    let last = events.last()?;
+    Some(EventCursor {
+        timestamp: last.timestamp.clone()?,
+        id: last.id.clone()?,
+    })
+}
+
+/// Lets `instrument` attach a `row_count` span field and metric to every
+/// `GraphStore` operation generically, regardless of whether it returns a
+/// single record, a collection, or nothing.
+trait RowCount {
+    fn row_count(&self) -> u64;
+}
+
+impl RowCount for () {
+    fn row_count(&self) -> u64 {
+        0
+    }
+}
+
+impl RowCount for String {
+    fn row_count(&self) -> u64 {
+        1
+    }
+}
+
+impl RowCount for i64 {
+    fn row_count(&self) -> u64 {
+        1
+    }
+}
+
+impl<T> RowCount for Vec<T> {
+    fn row_count(&self) -> u64 {
+        self.len() as u64
+    }
+}
+
+impl<T> RowCount for Option<T> {
+    fn row_count(&self) -> u64 {
+        self.is_some() as u64
+    }
+}
+
+impl RowCount for EventPage {
+    fn row_count(&self) -> u64 {
+        self.events.len() as u64
+    }
+}
+
+impl RowCount for ProjectStats {
+    fn row_count(&self) -> u64 {
+        1
+    }
+}
+
+/// Query duration and row-count histograms, a queries-by-operation counter,
+/// and a failure counter for every `GraphStore` operation, plus gauges for
+/// connection state, write-ahead queue depth, and connection pool
+/// occupancy. Built once per
+/// `MemgraphStore` and shipped to the OTLP collector at
+/// `GraphDbConfig::otlp_endpoint` — traces and metrics share this one
+/// pipeline, so there's no separate per-call logging to maintain alongside
+/// it.
+struct GraphTelemetry {
+    duration_ms: Histogram<f64>,
+    rows: Histogram<u64>,
+    queries_total: Counter<u64>,
+    failures: Counter<u64>,
+}
+
+impl GraphTelemetry {
+    /// Build the OTLP trace/metric pipelines and register the
+    /// connection/queue-depth/pool-occupancy gauges. Returns `None` when
+    /// `endpoint` is unset, so callers can treat telemetry as a plain
+    /// `Option`.
+    fn init(
+        endpoint: &str,
+        connected: Arc<AtomicBool>,
+        wal: Arc<WriteAheadQueue>,
+        active_queries: Arc<AtomicI64>,
+    ) -> Option<Self> {
+        let span_exporter = opentelemetry_otlp::SpanExporter::builder()
+            .with_tonic()
+            .with_endpoint(endpoint)
+            .build()
+            .map_err(|e| tracing::warn!("Failed to build OTLP span exporter: {}", e))
+            .ok()?;
+        let tracer_provider = opentelemetry_sdk::trace::TracerProvider::builder()
+            .with_batch_exporter(span_exporter, opentelemetry_sdk::runtime::Tokio)
+            .build();
+        opentelemetry::global::set_tracer_provider(tracer_provider);
+
+        let metric_exporter = opentelemetry_otlp::MetricExporter::builder()
+            .with_tonic()
+            .with_endpoint(endpoint)
+            .build()
+            .map_err(|e| tracing::warn!("Failed to build OTLP metric exporter: {}", e))
+            .ok()?;
+        let meter_provider = opentelemetry_sdk::metrics::SdkMeterProvider::builder()
+            .with_periodic_exporter(metric_exporter)
+            .build();
+        opentelemetry::global::set_meter_provider(meter_provider);
+
+        let meter = opentelemetry::global::meter("ijoka.graph_db");
+        let duration_ms = meter
+            .f64_histogram("graph.query.duration_ms")
+            .with_description("Duration of GraphStore operations in milliseconds")
+            .build();
+        let rows = meter
+            .u64_histogram("graph.query.rows")
+            .with_description("Rows returned by GraphStore read operations")
+            .build();
+        let queries_total = meter
+            .u64_counter("graph.query.count")
+            .with_description("Count of GraphStore operations by type")
+            .build();
+        let failures = meter
+            .u64_counter("graph.query.failures")
+            .with_description("Count of failed GraphStore operations")
+            .build();
+
+        let connection_up = meter
+            .u64_observable_gauge("graph.connection.up")
+            .with_description("1 while connected to the graph database, 0 while reconnecting")
+            .build();
+        let queue_depth = meter
+            .i64_observable_gauge("graph.wal.queue_depth")
+            .with_description("Write-ahead entries still waiting to be replayed")
+            .build();
+        let pool_occupancy = meter
+            .i64_observable_gauge("graph.pool.occupancy")
+            .with_description("GraphStore operations currently in flight against the connection pool")
+            .build();
+        meter
+            .register_callback(
+                &[
+                    connection_up.as_any(),
+                    queue_depth.as_any(),
+                    pool_occupancy.as_any(),
+                ],
+                move |obs| {
+                    obs.observe_u64(&connection_up, connected.load(Ordering::SeqCst) as u64, &[]);
+                    obs.observe_i64(&queue_depth, wal.depth().unwrap_or(0), &[]);
+                    obs.observe_i64(&pool_occupancy, active_queries.load(Ordering::SeqCst), &[]);
+                },
+            )
+            .ok();
+
+        Some(Self {
+            duration_ms,
+            rows,
+            queries_total,
+            failures,
+        })
+    }
+
+    /// Record one operation's duration and row count, tagging the queries
+    /// and (on failure) the failures counters.
+    fn record(&self, op: &'static str, started: Instant, ok: bool, row_count: u64) {
+        let attrs = [KeyValue::new("operation", op)];
+        self.duration_ms
+            .record(started.elapsed().as_secs_f64() * 1000.0, &attrs);
+        self.rows.record(row_count, &attrs);
+        self.queries_total.add(1, &attrs);
+        if !ok {
+            self.failures.add(1, &attrs);
+        }
+    }
+}
+
+// =============================================================================
+// SNAPSHOT EXPORT/IMPORT
+// =============================================================================
+
+/// Schema version stamped into every snapshot's `manifest.json`. Bump this
+/// whenever a node/relationship shape written by `MemgraphStore::dump`
+/// changes in a way `restore` needs to know about.
+const SNAPSHOT_SCHEMA_VERSION: u32 = 1;
+/// Rows fetched per page while streaming a label or relationship to disk, so
+/// a large graph is dumped/restored without holding it all in memory at once.
+const SNAPSHOT_PAGE_SIZE: i64 = 500;
+
+/// Node labels covered by `MemgraphStore::dump`/`restore`, in the order
+/// they're written and restored so an edge's endpoints already exist by the
+/// time the edge itself is restored.
+const SNAPSHOT_NODE_LABELS: &[&str] = &["Project", "Feature", "Event", "Session", "Insight", "Rule"];
+
+/// One relationship type dumped/restored by `MemgraphStore::dump`/`restore`:
+/// its name, the labels of the nodes it connects, and the property each side
+/// is matched on. Memgraph doesn't expose a stable relationship id the way
+/// nodes expose `id`, so edges are re-derived from their endpoints' keys
+/// instead of replayed directly.
+struct SnapshotRelationship {
+    name: &'static str,
+    from_label: &'static str,
+    from_key: &'static str,
+    to_label: &'static str,
+    to_key: &'static str,
+}
+
+const SNAPSHOT_RELATIONSHIPS: &[SnapshotRelationship] = &[
+    SnapshotRelationship {
+        name: "BELONGS_TO",
+        from_label: "Feature",
+        from_key: "id",
+        to_label: "Project",
+        to_key: "path",
+    },
+    SnapshotRelationship {
+        name: "IN_PROJECT",
+        from_label: "Session",
+        from_key: "id",
+        to_label: "Project",
+        to_key: "path",
+    },
+    SnapshotRelationship {
+        name: "TRIGGERED_BY",
+        from_label: "Event",
+        from_key: "id",
+        to_label: "Session",
+        to_key: "id",
+    },
+    SnapshotRelationship {
+        name: "LINKED_TO",
+        from_label: "Event",
+        from_key: "id",
+        to_label: "Feature",
+        to_key: "id",
+    },
+    SnapshotRelationship {
+        name: "LEARNED_FROM",
+        from_label: "Insight",
+        from_key: "id",
+        to_label: "Event",
+        to_key: "id",
+    },
+    SnapshotRelationship {
+        name: "APPLIES_TO",
+        from_label: "Rule",
+        from_key: "id",
+        to_label: "Project",
+        to_key: "path",
+    },
+    SnapshotRelationship {
+        name: "DERIVED_FROM",
+        from_label: "Rule",
+        from_key: "id",
+        to_label: "Insight",
+        to_key: "id",
+    },
+];
+
+/// One dumped relationship row: the matched key of its `from` and `to`
+/// endpoints (e.g. a `Feature.id` and the `Project.path` it `BELONGS_TO`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SnapshotEdge {
+    from: String,
+    to: String,
+}
+
+/// `manifest.json` written alongside the per-label/relationship NDJSON files
+/// by `MemgraphStore::dump`, and checked by `restore` before touching the
+/// graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SnapshotManifest {
+    schema_version: u32,
+    created_at: String,
+    /// Row counts written per NDJSON file, keyed by node label or
+    /// relationship name.
+    counts: std::collections::BTreeMap<String, i64>,
+}
+
+/// Progress/status handle returned by `MemgraphStore::dump` and `restore`,
+/// pollable the same way `queue_depth` lets a caller watch the write-ahead
+/// queue without blocking on it, so a dump/restore endpoint can report task
+/// state instead of holding the request open for the whole operation.
+pub struct SnapshotHandle {
+    stage: Mutex<String>,
+    exported: AtomicI64,
+    total: AtomicI64,
+    done: AtomicBool,
+    error: Mutex<Option<String>>,
+}
+
+impl SnapshotHandle {
+    fn new() -> Arc<Self> {
+        Arc::new(Self {
+            stage: Mutex::new("starting".to_string()),
+            exported: AtomicI64::new(0),
+            total: AtomicI64::new(0),
+            done: AtomicBool::new(false),
+            error: Mutex::new(None),
+        })
+    }
+
+    fn set_stage(&self, stage: &str) {
+        *self.stage.lock().unwrap() = stage.to_string();
+    }
+
+    fn set_total(&self, total: i64) {
+        self.total.store(total, Ordering::SeqCst);
+    }
+
+    fn add_progress(&self, n: i64) {
+        self.exported.fetch_add(n, Ordering::SeqCst);
+    }
+
+    fn finish(&self, result: &Result<()>) {
+        if let Err(e) = result {
+            *self.error.lock().unwrap() = Some(e.to_string());
         }
+        self.done.store(true, Ordering::SeqCst);
+    }
+
+    /// The node label or relationship currently being processed.
+    pub fn stage(&self) -> String {
+        self.stage.lock().unwrap().clone()
+    }
+
+    /// Rows written (dump) or applied (restore) so far.
+    pub fn exported(&self) -> i64 {
+        self.exported.load(Ordering::SeqCst)
+    }
+
+    /// Total rows expected, when known upfront (`restore` reads this from
+    /// the manifest; `dump` doesn't know it ahead of time, so this stays 0).
+    pub fn total(&self) -> i64 {
+        self.total.load(Ordering::SeqCst)
+    }
+
+    /// `true` once the dump/restore has finished, successfully or not.
+    pub fn is_done(&self) -> bool {
+        self.done.load(Ordering::SeqCst)
+    }
+
+    /// The failure, if the operation ended in an error.
+    pub fn error(&self) -> Option<String> {
+        self.error.lock().unwrap().clone()
     }
 }
 
-impl GraphDb {
-    /// Create a new GraphDb instance with default config
+impl MemgraphStore {
+    /// Create a new MemgraphStore instance with default config
     pub fn new() -> Self {
         Self::with_config(GraphDbConfig::default())
     }
 
-    /// Create a new GraphDb instance with custom config
+    /// Create a new MemgraphStore instance with custom config
     pub fn with_config(config: GraphDbConfig) -> Self {
+        let wal = Arc::new(
+            WriteAheadQueue::open(&config.wal_path).expect("Failed to open graph write-ahead queue"),
+        );
+        let connected = Arc::new(AtomicBool::new(false));
+        let active_queries = Arc::new(AtomicI64::new(0));
+        let telemetry = config.otlp_endpoint.as_deref().and_then(|endpoint| {
+            GraphTelemetry::init(
+                endpoint,
+                Arc::clone(&connected),
+                Arc::clone(&wal),
+                Arc::clone(&active_queries),
+            )
+        });
+
         Self {
             graph: Arc::new(RwLock::new(None)),
+            connected,
+            wal,
+            active_queries,
+            telemetry,
             config,
         }
     }
 
+    /// Run a `GraphStore` operation inside an `info_span!` carrying the
+    /// operation name, its row count, and (for queries with a
+    /// caller-composed `WHERE` clause) its parameter cardinality, recording
+    /// duration and row count and incrementing the queries/failures
+    /// counters. A no-op beyond the span when telemetry is disabled.
+    async fn instrument<T: RowCount>(
+        &self,
+        op: &'static str,
+        fut: impl std::future::Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let span = tracing::info_span!(
+            "graph_op",
+            operation = op,
+            row_count = tracing::field::Empty,
+            param_count = tracing::field::Empty,
+        );
+        async move {
+            self.active_queries.fetch_add(1, Ordering::SeqCst);
+            let started = Instant::now();
+            let result = fut.await;
+            self.active_queries.fetch_sub(1, Ordering::SeqCst);
+            let row_count = result.as_ref().map(RowCount::row_count).unwrap_or(0);
+            tracing::Span::current().record("row_count", row_count);
+            if let Some(telemetry) = &self.telemetry {
+                telemetry.record(op, started, result.is_ok(), row_count);
+            }
+            result
+        }
+        .instrument(span)
+        .await
+    }
+
     /// Connect to the graph database
     pub async fn connect(&self) -> Result<()> {
         tracing::info!("Connecting to graph database at {}", self.config.uri);
@@ -62,6 +660,7 @@ impl GraphDb {
             .user(&self.config.user)
             .password(&self.config.password)
             .db("memgraph") // Memgraph only accepts "memgraph" as db name
+            .max_connections(self.config.pool_max_size)
             .build()
             .context("Failed to build graph config")?;
 
@@ -77,915 +676,2272 @@ impl GraphDb {
 
         let mut guard = self.graph.write().await;
         *guard = Some(graph);
+        drop(guard);
+        self.connected.store(true, Ordering::SeqCst);
+
+        self.drain_wal().await;
 
         Ok(())
     }
 
     /// Check if connected to the graph database
     pub async fn is_connected(&self) -> bool {
-        self.graph.read().await.is_some()
+        self.connected.load(Ordering::SeqCst)
     }
 
-    /// Get the graph connection (panics if not connected)
-    async fn get_graph(&self) -> Result<Graph> {
-        let guard = self.graph.read().await;
-        guard
-            .clone()
-            .ok_or_else(|| anyhow::anyhow!("Not connected to graph database"))
+    /// Number of queued writes still waiting to be replayed against the
+    /// graph (non-zero means we're behind, or currently disconnected).
+    pub fn queue_depth(&self) -> i64 {
+        self.wal.depth().unwrap_or(0)
     }
 
-    // =========================================================================
-    // PROJECT OPERATIONS
-    // =========================================================================
-
-    /// Create or update a project
-    pub async fn upsert_project(&self, project: &Project) -> Result<()> {
-        let graph = self.get_graph().await?;
+    /// Spawn a background task that periodically probes the connection
+    /// (`RETURN 1`) while connected, and on failure (or while already
+    /// disconnected) retries with exponential backoff up to
+    /// `GraphDbConfig::reconnect_max_backoff`, draining the write-ahead queue
+    /// on every successful reconnect.
+    pub fn spawn_reconnect_supervisor(self: &Arc<Self>) {
+        let db = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut backoff = RECONNECT_INITIAL_BACKOFF;
+
+            loop {
+                if db.connected.load(Ordering::SeqCst) {
+                    if db.probe().await {
+                        tokio::time::sleep(LIVENESS_PROBE_INTERVAL).await;
+                        continue;
+                    }
+
+                    tracing::warn!("Lost connection to graph database, reconnecting");
+                    db.connected.store(false, Ordering::SeqCst);
+                    *db.graph.write().await = None;
+                    backoff = RECONNECT_INITIAL_BACKOFF;
+                }
+
+                match db.connect().await {
+                    Ok(()) => {
+                        backoff = RECONNECT_INITIAL_BACKOFF;
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Graph reconnect attempt failed ({:?} backoff): {}",
+                            backoff,
+                            e
+                        );
+                        tokio::time::sleep(backoff).await;
+                        backoff = (backoff * 2).min(db.config.reconnect_max_backoff);
+                    }
+                }
+            }
+        });
+    }
 
-        let q = query(
-            r#"
-            MERGE (p:Project {path: $path})
-            ON CREATE SET
-                p.id = $id,
-                p.name = $name,
-                p.description = $description,
-                p.created_at = datetime(),
-                p.updated_at = datetime(),
-                p.settings = $settings
-            ON MATCH SET
-                p.name = $name,
-                p.description = $description,
-                p.updated_at = datetime(),
-                p.settings = $settings
-            RETURN p
-            "#,
-        )
-        .param("id", project.id.clone())
-        .param("path", project.path.clone())
-        .param("name", project.name.clone())
-        .param("description", project.description.clone().unwrap_or_default())
-        .param(
-            "settings",
-            serde_json::to_string(&project.settings).unwrap_or_default(),
-        );
+    /// Run the `RETURN 1` liveness probe against the current connection.
+    async fn probe(&self) -> bool {
+        let guard = self.graph.read().await;
+        let Some(graph) = guard.as_ref() else {
+            return false;
+        };
 
-        graph.run(q).await?;
-        Ok(())
+        match graph.execute(query("RETURN 1 as n")).await {
+            Ok(mut result) => result.next().await.map(|row| row.is_some()).unwrap_or(false),
+            Err(_) => false,
+        }
     }
 
-    /// Get all projects
-    pub async fn get_projects(&self) -> Result<Vec<Project>> {
-        let graph = self.get_graph().await?;
-
-        let q = query("MATCH (p:Project) RETURN p ORDER BY p.name");
-        let mut result = graph.execute(q).await?;
+    /// Replay every pending write-ahead entry against the now-live graph,
+    /// in sequence order, marking each applied as it succeeds.
+    async fn drain_wal(&self) {
+        let pending = match self.wal.pending() {
+            Ok(p) => p,
+            Err(e) => {
+                tracing::error!("Failed to read graph write-ahead queue: {}", e);
+                return;
+            }
+        };
 
-        let mut projects = Vec::new();
-        while let Some(row) = result.next().await? {
-            let node: Node = row.get("p")?;
-            projects.push(Project::from_node(&node)?);
+        if pending.is_empty() {
+            return;
         }
 
-        Ok(projects)
-    }
-
-    /// Get project by path
-    pub async fn get_project_by_path(&self, path: &str) -> Result<Option<Project>> {
-        let graph = self.get_graph().await?;
+        tracing::info!("Replaying {} queued graph write(s)", pending.len());
 
-        let q = query("MATCH (p:Project {path: $path}) RETURN p").param("path", path);
-        let mut result = graph.execute(q).await?;
+        let guard = self.graph.read().await;
+        let Some(graph) = guard.as_ref() else {
+            return;
+        };
 
-        if let Some(row) = result.next().await? {
-            let node: Node = row.get("p")?;
-            Ok(Some(Project::from_node(&node)?))
-        } else {
-            Ok(None)
+        for (seq, cypher, bound_params) in pending {
+            let q = rebuild_query(&cypher, &bound_params);
+            match graph.run(q).await {
+                Ok(()) => {
+                    if let Err(e) = self.wal.mark_applied(seq) {
+                        tracing::error!("Failed to mark wal entry {} applied: {}", seq, e);
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Failed to replay queued graph write {}: {}", seq, e);
+                    break;
+                }
+            }
         }
     }
 
-    // =========================================================================
-    // FEATURE OPERATIONS
-    // =========================================================================
+    /// Get the graph connection (returns an error if not connected)
+    async fn get_graph(&self) -> Result<Graph> {
+        let guard = self.graph.read().await;
+        guard
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Not connected to graph database"))
+    }
 
-    /// Create a new feature linked to a project
-    pub async fn create_feature(&self, feature: &Feature, project_path: &str) -> Result<String> {
-        let graph = self.get_graph().await?;
+    /// Run a mutating Cypher statement. While disconnected, the statement
+    /// is appended to the durable write-ahead queue instead of failing, so
+    /// writes survive transient Memgraph/Bolt outages and are replayed in
+    /// order once `connect` succeeds again.
+    async fn execute_mutation(&self, cypher: &str, bound_params: Vec<(String, WalValue)>) -> Result<()> {
+        if !self.connected.load(Ordering::SeqCst) {
+            self.wal.enqueue(cypher, &bound_params)?;
+            return Ok(());
+        }
 
-        let feature_id = feature
-            .id
-            .clone()
-            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let guard = self.graph.read().await;
+        let Some(graph) = guard.as_ref() else {
+            drop(guard);
+            self.connected.store(false, Ordering::SeqCst);
+            self.wal.enqueue(cypher, &bound_params)?;
+            return Ok(());
+        };
 
-        let q = query(
-            r#"
-            MATCH (p:Project {path: $project_path})
-            CREATE (f:Feature {
-                id: $id,
-                description: $description,
-                category: $category,
-                status: $status,
-                priority: $priority,
-                steps: $steps,
-                created_at: datetime(),
-                updated_at: datetime(),
-                work_count: 0
-            })-[:BELONGS_TO]->(p)
-            RETURN f.id as id
-            "#,
-        )
-        .param("project_path", project_path)
-        .param("id", feature_id.clone())
-        .param("description", feature.description.clone())
-        .param("category", feature.category.clone())
-        .param("status", feature.status.clone())
-        .param("priority", feature.priority.unwrap_or(0) as i64)
-        .param("steps", feature.steps.clone().unwrap_or_default());
-
-        graph.run(q).await?;
-        Ok(feature_id)
+        let q = rebuild_query(cypher, &bound_params);
+        match graph.run(q).await {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                drop(guard);
+                tracing::warn!("Graph write failed, queuing for replay: {}", e);
+                self.connected.store(false, Ordering::SeqCst);
+                self.wal.enqueue(cypher, &bound_params)?;
+                Ok(())
+            }
+        }
     }
 
-    /// Get all features for a project
-    pub async fn get_features_for_project(&self, project_path: &str) -> Result<Vec<Feature>> {
-        let graph = self.get_graph().await?;
+    /// Run several mutating Cypher statements as one atomic transaction, so
+    /// compound writes like "create a node, then link it" either fully
+    /// commit or fully roll back instead of leaving a half-written graph
+    /// when the second statement fails. While disconnected (or if starting
+    /// the transaction fails), every statement is appended to the
+    /// write-ahead queue as a group instead of being attempted, same as
+    /// `execute_mutation`.
+    async fn execute_txn(&self, statements: Vec<(&str, Vec<(String, WalValue)>)>) -> Result<()> {
+        if !self.connected.load(Ordering::SeqCst) {
+            for (cypher, bound_params) in &statements {
+                self.wal.enqueue(cypher, bound_params)?;
+            }
+            return Ok(());
+        }
 
-        let q = query(
-            r#"
-            MATCH (f:Feature)-[:BELONGS_TO]->(p:Project {path: $project_path})
-            RETURN f
-            ORDER BY f.priority DESC, f.created_at DESC
-            "#,
-        )
-        .param("project_path", project_path);
+        let guard = self.graph.read().await;
+        let Some(graph) = guard.as_ref() else {
+            drop(guard);
+            self.connected.store(false, Ordering::SeqCst);
+            for (cypher, bound_params) in &statements {
+                self.wal.enqueue(cypher, bound_params)?;
+            }
+            return Ok(());
+        };
 
-        let mut result = graph.execute(q).await?;
+        let mut txn = match graph.start_txn().await {
+            Ok(txn) => txn,
+            Err(e) => {
+                drop(guard);
+                tracing::warn!("Failed to start graph transaction, queuing for replay: {}", e);
+                self.connected.store(false, Ordering::SeqCst);
+                for (cypher, bound_params) in &statements {
+                    self.wal.enqueue(cypher, bound_params)?;
+                }
+                return Ok(());
+            }
+        };
 
-        let mut features = Vec::new();
-        while let Some(row) = result.next().await? {
-            let node: Node = row.get("f")?;
-            let mut feature = Feature::from_node(&node)?;
-            feature.project_dir = Some(project_path.to_string());
-            features.push(feature);
+        for (cypher, bound_params) in &statements {
+            let q = rebuild_query(cypher, bound_params);
+            if let Err(e) = txn.run(q).await {
+                drop(guard);
+                tracing::warn!(
+                    "Graph transaction failed, rolling back and queuing for replay: {}",
+                    e
+                );
+                let _ = txn.rollback().await;
+                self.connected.store(false, Ordering::SeqCst);
+                for (cypher, bound_params) in &statements {
+                    self.wal.enqueue(cypher, bound_params)?;
+                }
+                return Ok(());
+            }
         }
 
-        Ok(features)
+        drop(guard);
+        txn.commit().await.context("Failed to commit graph transaction")?;
+        Ok(())
     }
 
-    /// Get active feature for a project (status = 'in_progress')
-    pub async fn get_active_feature(&self, project_path: &str) -> Result<Option<Feature>> {
-        let graph = self.get_graph().await?;
+    /// Stream every node and relationship covered by `SNAPSHOT_NODE_LABELS` /
+    /// `SNAPSHOT_RELATIONSHIPS` into `dir` as one NDJSON file per label or
+    /// relationship, plus a `manifest.json` recording the schema version and
+    /// row counts. Runs on a background task so exporting a large graph
+    /// doesn't block the caller; poll the returned handle for progress.
+    pub fn dump(self: &Arc<Self>, dir: impl Into<String>) -> Arc<SnapshotHandle> {
+        let db = Arc::clone(self);
+        let dir = dir.into();
+        let handle = SnapshotHandle::new();
+        let task_handle = Arc::clone(&handle);
+
+        tokio::spawn(async move {
+            let result = db.dump_inner(&dir, &task_handle).await;
+            task_handle.finish(&result);
+        });
+
+        handle
+    }
 
-        let q = query(
-            r#"
-            MATCH (f:Feature {status: 'in_progress'})-[:BELONGS_TO]->(p:Project {path: $project_path})
-            RETURN f
-            LIMIT 1
-            "#,
-        )
-        .param("project_path", project_path);
+    async fn dump_inner(&self, dir: &str, progress: &SnapshotHandle) -> Result<()> {
+        let dir = std::path::Path::new(dir);
+        std::fs::create_dir_all(dir).context("Failed to create snapshot directory")?;
 
-        let mut result = graph.execute(q).await?;
+        let mut counts = std::collections::BTreeMap::new();
 
-        if let Some(row) = result.next().await? {
-            let node: Node = row.get("f")?;
-            Ok(Some(Feature::from_node(&node)?))
-        } else {
-            Ok(None)
+        for label in SNAPSHOT_NODE_LABELS {
+            progress.set_stage(label);
+            let count = self.dump_node_label(dir, label, progress).await?;
+            counts.insert((*label).to_string(), count);
         }
-    }
 
-    /// Update feature status
-    pub async fn update_feature_status(&self, feature_id: &str, status: &str) -> Result<()> {
-        let graph = self.get_graph().await?;
+        for rel in SNAPSHOT_RELATIONSHIPS {
+            progress.set_stage(rel.name);
+            let count = self.dump_relationship(dir, rel, progress).await?;
+            counts.insert(rel.name.to_string(), count);
+        }
 
-        let q = query(
-            r#"
-            MATCH (f:Feature {id: $id})
-            SET f.status = $status, f.updated_at = datetime()
-            "#,
+        let manifest = SnapshotManifest {
+            schema_version: SNAPSHOT_SCHEMA_VERSION,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            counts,
+        };
+        std::fs::write(
+            dir.join("manifest.json"),
+            serde_json::to_string_pretty(&manifest)?,
         )
-        .param("id", feature_id)
-        .param("status", status);
+        .context("Failed to write snapshot manifest")?;
 
-        graph.run(q).await?;
         Ok(())
     }
 
-    /// Activate a feature (set to in_progress)
-    /// Multiple features can be in_progress simultaneously
-    pub async fn activate_feature(&self, _project_path: &str, feature_id: &str) -> Result<()> {
+    /// Page through every `label` node, writing one JSON line per node to
+    /// `<dir>/<label>.ndjson`.
+    async fn dump_node_label(
+        &self,
+        dir: &std::path::Path,
+        label: &str,
+        progress: &SnapshotHandle,
+    ) -> Result<i64> {
         let graph = self.get_graph().await?;
+        let file = std::fs::File::create(dir.join(format!("{label}.ndjson")))
+            .with_context(|| format!("Failed to create {label}.ndjson"))?;
+        let mut writer = std::io::BufWriter::new(file);
+        let mut skip: i64 = 0;
+        let mut written: i64 = 0;
+
+        loop {
+            let q = query(&format!(
+                "MATCH (n:{label}) RETURN n ORDER BY n.id SKIP $skip LIMIT $limit"
+            ))
+            .param("skip", skip)
+            .param("limit", SNAPSHOT_PAGE_SIZE);
+            let mut result = graph.execute(q).await?;
+
+            let mut page = 0i64;
+            while let Some(row) = result.next().await? {
+                let node: Node = row.get("n")?;
+                let line = match label {
+                    "Project" => serde_json::to_string(&Project::from_node(&node)?)?,
+                    "Feature" => serde_json::to_string(&Feature::from_node(&node)?)?,
+                    "Event" => serde_json::to_string(&Event::from_node(&node)?)?,
+                    "Session" => serde_json::to_string(&Session::from_node(&node)?)?,
+                    "Insight" => serde_json::to_string(&Insight::from_node(&node)?)?,
+                    "Rule" => serde_json::to_string(&Rule::from_node(&node)?)?,
+                    other => anyhow::bail!("unknown snapshot node label: {other}"),
+                };
+                writeln!(writer, "{line}")?;
+                page += 1;
+            }
 
-        // Activate the specified feature (no longer deactivates others)
-        let q = query(
-            r#"
-            MATCH (f:Feature {id: $id})
-            SET f.status = 'in_progress', f.updated_at = datetime()
-            "#,
-        )
-        .param("id", feature_id);
-        graph.run(q).await?;
+            written += page;
+            progress.add_progress(page);
+            if page < SNAPSHOT_PAGE_SIZE {
+                break;
+            }
+            skip += SNAPSHOT_PAGE_SIZE;
+        }
 
-        Ok(())
+        writer.flush()?;
+        Ok(written)
     }
 
-    /// Complete a feature
-    pub async fn complete_feature(&self, feature_id: &str) -> Result<()> {
+    /// Page through every `rel` edge, writing one `SnapshotEdge` JSON line
+    /// per edge to `<dir>/<rel.name>.ndjson`.
+    async fn dump_relationship(
+        &self,
+        dir: &std::path::Path,
+        rel: &SnapshotRelationship,
+        progress: &SnapshotHandle,
+    ) -> Result<i64> {
         let graph = self.get_graph().await?;
+        let file = std::fs::File::create(dir.join(format!("{}.ndjson", rel.name)))
+            .with_context(|| format!("Failed to create {}.ndjson", rel.name))?;
+        let mut writer = std::io::BufWriter::new(file);
+        let mut skip: i64 = 0;
+        let mut written: i64 = 0;
+
+        loop {
+            let cypher = format!(
+                "MATCH (a:{from_label})-[:{rel}]->(b:{to_label}) \
+                 RETURN a.{from_key} as from_id, b.{to_key} as to_id \
+                 ORDER BY from_id, to_id SKIP $skip LIMIT $limit",
+                from_label = rel.from_label,
+                rel = rel.name,
+                to_label = rel.to_label,
+                from_key = rel.from_key,
+                to_key = rel.to_key,
+            );
+            let q = query(&cypher)
+                .param("skip", skip)
+                .param("limit", SNAPSHOT_PAGE_SIZE);
+            let mut result = graph.execute(q).await?;
+
+            let mut page = 0i64;
+            while let Some(row) = result.next().await? {
+                let edge = SnapshotEdge {
+                    from: row.get::<String>("from_id")?,
+                    to: row.get::<String>("to_id")?,
+                };
+                writeln!(writer, "{}", serde_json::to_string(&edge)?)?;
+                page += 1;
+            }
 
-        let q = query(
-            r#"
-            MATCH (f:Feature {id: $id})
-            SET f.status = 'complete', f.completed_at = datetime(), f.updated_at = datetime()
-            "#,
-        )
-        .param("id", feature_id);
+            written += page;
+            progress.add_progress(page);
+            if page < SNAPSHOT_PAGE_SIZE {
+                break;
+            }
+            skip += SNAPSHOT_PAGE_SIZE;
+        }
 
-        graph.run(q).await?;
-        Ok(())
+        writer.flush()?;
+        Ok(written)
     }
 
-    /// Increment work count for a feature
-    pub async fn increment_work_count(&self, feature_id: &str) -> Result<i64> {
-        let graph = self.get_graph().await?;
+    /// Reconstruct the graph from a directory written by `dump`, `MERGE`-ing
+    /// every node on its stable id (or `path`, for `Project`) and every
+    /// relationship on its endpoints' keys, so restoring into a graph that
+    /// already has some of the data creates no duplicates; a node that
+    /// already exists has its properties overwritten with the dumped values.
+    /// Runs on a background task for the same reason `dump` does.
+    pub fn restore(self: &Arc<Self>, dir: impl Into<String>) -> Arc<SnapshotHandle> {
+        let db = Arc::clone(self);
+        let dir = dir.into();
+        let handle = SnapshotHandle::new();
+        let task_handle = Arc::clone(&handle);
+
+        tokio::spawn(async move {
+            let result = db.restore_inner(&dir, &task_handle).await;
+            task_handle.finish(&result);
+        });
+
+        handle
+    }
 
-        let q = query(
-            r#"
-            MATCH (f:Feature {id: $id})
-            SET f.work_count = f.work_count + 1, f.updated_at = datetime()
-            RETURN f.work_count as count
-            "#,
-        )
-        .param("id", feature_id);
+    async fn restore_inner(&self, dir: &str, progress: &SnapshotHandle) -> Result<()> {
+        let dir = std::path::Path::new(dir);
+        let manifest_json = std::fs::read_to_string(dir.join("manifest.json"))
+            .context("Failed to read snapshot manifest")?;
+        let manifest: SnapshotManifest =
+            serde_json::from_str(&manifest_json).context("Failed to parse snapshot manifest")?;
+
+        if manifest.schema_version != SNAPSHOT_SCHEMA_VERSION {
+            anyhow::bail!(
+                "Snapshot schema version {} is not supported (expected {})",
+                manifest.schema_version,
+                SNAPSHOT_SCHEMA_VERSION
+            );
+        }
 
-        let mut result = graph.execute(q).await?;
-        if let Some(row) = result.next().await? {
-            Ok(row.get::<i64>("count")?)
-        } else {
-            Ok(0)
+        progress.set_total(manifest.counts.values().sum());
+
+        for label in SNAPSHOT_NODE_LABELS {
+            progress.set_stage(label);
+            self.restore_node_label(dir, label, progress).await?;
+        }
+
+        for rel in SNAPSHOT_RELATIONSHIPS {
+            progress.set_stage(rel.name);
+            self.restore_relationship(dir, rel, progress).await?;
         }
+
+        Ok(())
     }
 
-    // =========================================================================
-    // EVENT OPERATIONS
-    // =========================================================================
+    /// Replay every line of `<dir>/<label>.ndjson` as a `MERGE` keyed on
+    /// that label's stable identity. Missing files are treated as empty, so
+    /// a snapshot that never wrote a label (nothing of that kind existed
+    /// yet) restores cleanly.
+    async fn restore_node_label(
+        &self,
+        dir: &std::path::Path,
+        label: &str,
+        progress: &SnapshotHandle,
+    ) -> Result<i64> {
+        let path = dir.join(format!("{label}.ndjson"));
+        if !path.exists() {
+            return Ok(0);
+        }
 
-    /// Record an event
-    pub async fn record_event(&self, event: &Event, session_id: &str) -> Result<String> {
-        let graph = self.get_graph().await?;
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {label}.ndjson"))?;
+        let mut restored = 0i64;
 
-        let event_id = event
-            .id
-            .clone()
-            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        for line in content.lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
 
-        let q = query(
-            r#"
-            MATCH (s:Session {id: $session_id})
-            CREATE (e:Event {
-                id: $id,
-                event_type: $event_type,
-                tool_name: $tool_name,
-                payload: $payload,
-                summary: $summary,
-                timestamp: datetime(),
-                success: $success
-            })-[:TRIGGERED_BY]->(s)
-            RETURN e.id as id
-            "#,
-        )
-        .param("session_id", session_id)
-        .param("id", event_id.clone())
-        .param("event_type", event.event_type.clone())
-        .param("tool_name", event.tool_name.clone().unwrap_or_default())
-        .param(
-            "payload",
-            serde_json::to_string(&event.payload).unwrap_or_default(),
-        )
-        .param("summary", event.summary.clone().unwrap_or_default())
-        .param("success", event.success.unwrap_or(true));
+            match label {
+                "Project" => self.restore_project(serde_json::from_str(line)?).await?,
+                "Feature" => self.restore_feature(serde_json::from_str(line)?).await?,
+                "Event" => self.restore_event(serde_json::from_str(line)?).await?,
+                "Session" => self.restore_session(serde_json::from_str(line)?).await?,
+                "Insight" => self.restore_insight(serde_json::from_str(line)?).await?,
+                "Rule" => self.restore_rule(serde_json::from_str(line)?).await?,
+                other => anyhow::bail!("unknown snapshot node label: {other}"),
+            }
 
-        graph.run(q).await?;
-        Ok(event_id)
+            restored += 1;
+            progress.add_progress(1);
+        }
+
+        Ok(restored)
     }
 
-    /// Link an event to a feature
-    pub async fn link_event_to_feature(&self, event_id: &str, feature_id: &str) -> Result<()> {
-        let graph = self.get_graph().await?;
+    async fn restore_project(&self, project: Project) -> Result<()> {
+        const CYPHER: &str = r#"
+            MERGE (p:Project {path: $path})
+            SET p.id = $id,
+                p.name = $name,
+                p.description = $description,
+                p.created_at = $created_at,
+                p.updated_at = $updated_at,
+                p.settings = $settings
+            "#;
+
+        self.execute_mutation(
+            CYPHER,
+            vec![
+                ("path".into(), WalValue::Text(project.path)),
+                ("id".into(), WalValue::Text(project.id)),
+                ("name".into(), WalValue::Text(project.name)),
+                (
+                    "description".into(),
+                    WalValue::Text(project.description.unwrap_or_default()),
+                ),
+                (
+                    "created_at".into(),
+                    WalValue::Text(project.created_at.unwrap_or_default()),
+                ),
+                (
+                    "updated_at".into(),
+                    WalValue::Text(project.updated_at.unwrap_or_default()),
+                ),
+                (
+                    "settings".into(),
+                    WalValue::Text(serde_json::to_string(&project.settings).unwrap_or_default()),
+                ),
+            ],
+        )
+        .await
+    }
 
-        let q = query(
-            r#"
-            MATCH (e:Event {id: $event_id}), (f:Feature {id: $feature_id})
-            MERGE (e)-[:LINKED_TO]->(f)
-            "#,
+    async fn restore_feature(&self, feature: Feature) -> Result<()> {
+        let Some(id) = feature.id else {
+            return Ok(());
+        };
+        const CYPHER: &str = r#"
+            MERGE (f:Feature {id: $id})
+            SET f.description = $description,
+                f.category = $category,
+                f.status = $status,
+                f.priority = $priority,
+                f.steps = $steps,
+                f.created_at = $created_at,
+                f.updated_at = $updated_at,
+                f.completed_at = $completed_at,
+                f.work_count = $work_count,
+                f.assigned_agent = $assigned_agent
+            "#;
+
+        self.execute_mutation(
+            CYPHER,
+            vec![
+                ("id".into(), WalValue::Text(id)),
+                ("description".into(), WalValue::Text(feature.description)),
+                ("category".into(), WalValue::Text(feature.category)),
+                ("status".into(), WalValue::Text(feature.status)),
+                (
+                    "priority".into(),
+                    WalValue::Int(feature.priority.unwrap_or(0) as i64),
+                ),
+                ("steps".into(), WalValue::List(feature.steps.unwrap_or_default())),
+                (
+                    "created_at".into(),
+                    WalValue::Text(feature.created_at.unwrap_or_default()),
+                ),
+                (
+                    "updated_at".into(),
+                    WalValue::Text(feature.updated_at.unwrap_or_default()),
+                ),
+                (
+                    "completed_at".into(),
+                    WalValue::Text(feature.completed_at.unwrap_or_default()),
+                ),
+                (
+                    "work_count".into(),
+                    WalValue::Int(feature.work_count.unwrap_or(0) as i64),
+                ),
+                (
+                    "assigned_agent".into(),
+                    WalValue::Text(feature.assigned_agent.unwrap_or_default()),
+                ),
+            ],
         )
-        .param("event_id", event_id)
-        .param("feature_id", feature_id);
+        .await
+    }
 
-        graph.run(q).await?;
-        Ok(())
+    async fn restore_event(&self, event: Event) -> Result<()> {
+        let Some(id) = event.id else {
+            return Ok(());
+        };
+        const CYPHER: &str = r#"
+            MERGE (e:Event {id: $id})
+            SET e.event_type = $event_type,
+                e.tool_name = $tool_name,
+                e.payload = $payload,
+                e.summary = $summary,
+                e.timestamp = $timestamp,
+                e.success = $success,
+                e.source_agent = $source_agent
+            "#;
+
+        self.execute_mutation(
+            CYPHER,
+            vec![
+                ("id".into(), WalValue::Text(id)),
+                ("event_type".into(), WalValue::Text(event.event_type)),
+                (
+                    "tool_name".into(),
+                    WalValue::Text(event.tool_name.unwrap_or_default()),
+                ),
+                (
+                    "payload".into(),
+                    WalValue::Text(
+                        event
+                            .payload
+                            .map(|p| serde_json::to_string(&p).unwrap_or_default())
+                            .unwrap_or_default(),
+                    ),
+                ),
+                ("summary".into(), WalValue::Text(event.summary.unwrap_or_default())),
+                (
+                    "timestamp".into(),
+                    WalValue::Text(event.timestamp.unwrap_or_default()),
+                ),
+                ("success".into(), WalValue::Bool(event.success.unwrap_or(false))),
+                (
+                    "source_agent".into(),
+                    WalValue::Text(event.source_agent.unwrap_or_default()),
+                ),
+            ],
+        )
+        .await
     }
 
-    /// Get recent events for a project
-    pub async fn get_recent_events(&self, project_path: &str, limit: i64) -> Result<Vec<Event>> {
-        let graph = self.get_graph().await?;
+    async fn restore_session(&self, session: Session) -> Result<()> {
+        const CYPHER: &str = r#"
+            MERGE (s:Session {id: $id})
+            SET s.agent = $agent,
+                s.status = $status,
+                s.started_at = $started_at,
+                s.ended_at = $ended_at,
+                s.last_activity = $last_activity,
+                s.event_count = $event_count,
+                s.is_subagent = $is_subagent
+            "#;
+
+        self.execute_mutation(
+            CYPHER,
+            vec![
+                ("id".into(), WalValue::Text(session.id)),
+                ("agent".into(), WalValue::Text(session.agent)),
+                ("status".into(), WalValue::Text(session.status)),
+                (
+                    "started_at".into(),
+                    WalValue::Text(session.started_at.unwrap_or_default()),
+                ),
+                (
+                    "ended_at".into(),
+                    WalValue::Text(session.ended_at.unwrap_or_default()),
+                ),
+                (
+                    "last_activity".into(),
+                    WalValue::Text(session.last_activity.unwrap_or_default()),
+                ),
+                (
+                    "event_count".into(),
+                    WalValue::Int(session.event_count.unwrap_or(0) as i64),
+                ),
+                (
+                    "is_subagent".into(),
+                    WalValue::Bool(session.is_subagent.unwrap_or(false)),
+                ),
+            ],
+        )
+        .await
+    }
 
-        let q = query(
-            r#"
-            MATCH (e:Event)-[:TRIGGERED_BY]->(s:Session)-[:IN_PROJECT]->(p:Project {path: $project_path})
-            OPTIONAL MATCH (e)-[:LINKED_TO]->(f:Feature)
-            RETURN e.id as id,
-                   e.event_type as event_type,
-                   e.tool_name as tool_name,
-                   e.payload as payload,
-                   e.summary as summary,
-                   toString(e.timestamp) as timestamp,
-                   e.success as success,
-                   e.source_agent as source_agent,
-                   s.id as session_id,
-                   p.path as project_path,
-                   f.id as feature_id,
-                   f.description as feature_description
-            ORDER BY e.timestamp DESC
-            LIMIT $limit
-            "#,
+    async fn restore_insight(&self, insight: Insight) -> Result<()> {
+        let Some(id) = insight.id else {
+            return Ok(());
+        };
+        const CYPHER: &str = r#"
+            MERGE (i:Insight {id: $id})
+            SET i.description = $description,
+                i.pattern_type = $pattern_type,
+                i.tags = $tags,
+                i.created_at = $created_at,
+                i.usage_count = $usage_count,
+                i.effectiveness_score = $effectiveness_score
+            "#;
+
+        self.execute_mutation(
+            CYPHER,
+            vec![
+                ("id".into(), WalValue::Text(id)),
+                ("description".into(), WalValue::Text(insight.description)),
+                ("pattern_type".into(), WalValue::Text(insight.pattern_type)),
+                ("tags".into(), WalValue::List(insight.tags.unwrap_or_default())),
+                (
+                    "created_at".into(),
+                    WalValue::Text(insight.created_at.unwrap_or_default()),
+                ),
+                (
+                    "usage_count".into(),
+                    WalValue::Int(insight.usage_count.unwrap_or(0) as i64),
+                ),
+                (
+                    "effectiveness_score".into(),
+                    WalValue::Float(insight.effectiveness_score.unwrap_or(0.0)),
+                ),
+            ],
         )
-        .param("project_path", project_path)
-        .param("limit", limit);
-
-        let mut result = graph.execute(q).await?;
-
-        let mut events = Vec::new();
-        while let Some(row) = result.next().await? {
-            let payload_str: Option<String> = row.get("payload").ok();
-            let payload: Option<serde_json::Value> = payload_str
-                .and_then(|s| serde_json::from_str(&s).ok());
-
-            events.push(Event {
-                id: row.get("id").ok(),
-                event_type: row.get("event_type")?,
-                tool_name: row.get("tool_name").ok(),
-                payload,
-                summary: row.get("summary").ok(),
-                timestamp: row.get("timestamp").ok(),
-                success: row.get("success").ok(),
-                source_agent: row.get("source_agent").ok(),
-                session_id: row.get("session_id").ok(),
-                project_path: row.get("project_path").ok(),
-                feature_id: row.get("feature_id").ok(),
-                feature_description: row.get("feature_description").ok(),
-            });
-        }
-
-        Ok(events)
-    }
-
-    /// Get recent events across all projects (global view)
-    pub async fn get_all_recent_events(&self, limit: i64) -> Result<Vec<Event>> {
-        let graph = self.get_graph().await?;
+        .await
+    }
 
-        let q = query(
-            r#"
-            MATCH (e:Event)
-            OPTIONAL MATCH (e)-[:TRIGGERED_BY]->(s:Session)-[:IN_PROJECT]->(p:Project)
-            OPTIONAL MATCH (e)-[:LINKED_TO]->(f:Feature)
-            RETURN e.id as id,
-                   e.event_type as event_type,
-                   e.tool_name as tool_name,
-                   e.payload as payload,
-                   e.summary as summary,
-                   toString(e.timestamp) as timestamp,
-                   e.success as success,
-                   e.source_agent as source_agent,
-                   s.id as session_id,
-                   p.path as project_path,
-                   f.id as feature_id,
-                   f.description as feature_description
-            ORDER BY e.timestamp DESC
-            LIMIT $limit
-            "#,
+    async fn restore_rule(&self, rule: Rule) -> Result<()> {
+        let Some(id) = rule.id else {
+            return Ok(());
+        };
+        const CYPHER: &str = r#"
+            MERGE (r:Rule {id: $id})
+            SET r.name = $name,
+                r.description = $description,
+                r.trigger = $trigger,
+                r.action = $action,
+                r.scope = $scope,
+                r.enforcement = $enforcement,
+                r.enabled = $enabled,
+                r.created_at = $created_at,
+                r.triggered_count = $triggered_count,
+                r.source_instruction_count = $source_instruction_count,
+                r.effectiveness_score = $effectiveness_score
+            "#;
+
+        self.execute_mutation(
+            CYPHER,
+            vec![
+                ("id".into(), WalValue::Text(id)),
+                ("name".into(), WalValue::Text(rule.name)),
+                ("description".into(), WalValue::Text(rule.description)),
+                (
+                    "trigger".into(),
+                    WalValue::Text(serde_json::to_string(&rule.trigger).unwrap_or_default()),
+                ),
+                (
+                    "action".into(),
+                    WalValue::Text(serde_json::to_string(&rule.action).unwrap_or_default()),
+                ),
+                ("scope".into(), WalValue::Text(rule.scope)),
+                ("enforcement".into(), WalValue::Text(rule.enforcement)),
+                ("enabled".into(), WalValue::Bool(rule.enabled.unwrap_or(true))),
+                (
+                    "created_at".into(),
+                    WalValue::Text(rule.created_at.unwrap_or_default()),
+                ),
+                (
+                    "triggered_count".into(),
+                    WalValue::Int(rule.triggered_count.unwrap_or(0) as i64),
+                ),
+                (
+                    "source_instruction_count".into(),
+                    WalValue::Int(rule.source_instruction_count.unwrap_or(0) as i64),
+                ),
+                (
+                    "effectiveness_score".into(),
+                    WalValue::Float(rule.effectiveness_score.unwrap_or(0.0)),
+                ),
+            ],
         )
-        .param("limit", limit);
+        .await
+    }
 
-        let mut result = graph.execute(q).await?;
+    /// Replay every line of `<dir>/<rel.name>.ndjson` as a `MERGE`'d edge
+    /// between its two endpoints, matched on the keys recorded by
+    /// `dump_relationship`. Missing files are treated as empty.
+    async fn restore_relationship(
+        &self,
+        dir: &std::path::Path,
+        rel: &SnapshotRelationship,
+        progress: &SnapshotHandle,
+    ) -> Result<i64> {
+        let path = dir.join(format!("{}.ndjson", rel.name));
+        if !path.exists() {
+            return Ok(0);
+        }
 
-        let mut events = Vec::new();
-        while let Some(row) = result.next().await? {
-            let payload_str: Option<String> = row.get("payload").ok();
-            let payload: Option<serde_json::Value> = payload_str
-                .and_then(|s| serde_json::from_str(&s).ok());
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}.ndjson", rel.name))?;
+        let cypher = format!(
+            "MATCH (a:{from_label} {{{from_key}: $from}}), (b:{to_label} {{{to_key}: $to}}) \
+             MERGE (a)-[:{rel}]->(b)",
+            from_label = rel.from_label,
+            from_key = rel.from_key,
+            to_label = rel.to_label,
+            to_key = rel.to_key,
+            rel = rel.name,
+        );
+        let mut restored = 0i64;
 
-            events.push(Event {
-                id: row.get("id").ok(),
-                event_type: row.get("event_type")?,
-                tool_name: row.get("tool_name").ok(),
-                payload,
-                summary: row.get("summary").ok(),
-                timestamp: row.get("timestamp").ok(),
-                success: row.get("success").ok(),
-                source_agent: row.get("source_agent").ok(),
-                session_id: row.get("session_id").ok(),
-                project_path: row.get("project_path").ok(),
-                feature_id: row.get("feature_id").ok(),
-                feature_description: row.get("feature_description").ok(),
-            });
+        for line in content.lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let edge: SnapshotEdge = serde_json::from_str(line)?;
+            self.execute_mutation(
+                &cypher,
+                vec![
+                    ("from".into(), WalValue::Text(edge.from)),
+                    ("to".into(), WalValue::Text(edge.to)),
+                ],
+            )
+            .await?;
+            restored += 1;
+            progress.add_progress(1);
         }
 
-        Ok(events)
+        Ok(restored)
     }
+}
 
-    /// Get events linked to a specific feature
-    pub async fn get_events_by_feature(&self, feature_id: &str, limit: i64) -> Result<Vec<Event>> {
-        let graph = self.get_graph().await?;
-
-        let q = query(
-            r#"
-            MATCH (e:Event)-[:LINKED_TO]->(f:Feature {id: $feature_id})
-            OPTIONAL MATCH (e)-[:TRIGGERED_BY]->(s:Session)-[:IN_PROJECT]->(p:Project)
-            RETURN e.id as id,
-                   e.event_type as event_type,
-                   e.tool_name as tool_name,
-                   e.payload as payload,
-                   e.summary as summary,
-                   toString(e.timestamp) as timestamp,
-                   e.success as success,
-                   e.source_agent as source_agent,
-                   s.id as session_id,
-                   p.path as project_path,
-                   f.description as feature_description
-            ORDER BY e.timestamp DESC
-            LIMIT $limit
-            "#,
-        )
-        .param("feature_id", feature_id)
-        .param("limit", limit);
-
-        let mut result = graph.execute(q).await?;
+/// The query-construction surface shared by every backing store: the
+/// Memgraph/neo4rs driver, the local SQLite cache, and the layered
+/// read-through/failover wrapper over both. Decoupling the surface from the
+/// driver is what lets `LayeredStore` serve reads from SQLite and defer
+/// writes to the graph without duplicating call sites.
+#[async_trait]
+pub trait GraphStore: Send + Sync {
+    async fn upsert_project(&self, project: &Project) -> Result<()>;
+    async fn get_projects(&self) -> Result<Vec<Project>>;
+    async fn get_project_by_path(&self, path: &str) -> Result<Option<Project>>;
+
+    async fn create_feature(&self, feature: &Feature, project_path: &str) -> Result<String>;
+    async fn get_features_for_project(&self, project_path: &str) -> Result<Vec<Feature>>;
+    async fn get_active_feature(&self, project_path: &str) -> Result<Option<Feature>>;
+    async fn update_feature_status(&self, feature_id: &str, status: &str) -> Result<()>;
+    async fn activate_feature(&self, project_path: &str, feature_id: &str) -> Result<()>;
+    async fn complete_feature(&self, feature_id: &str) -> Result<()>;
+    async fn increment_work_count(&self, feature_id: &str) -> Result<i64>;
+
+    async fn record_event(&self, event: &Event, session_id: &str) -> Result<String>;
+    /// Record many events in one round trip. Returns the generated ids in
+    /// the same order as `events`.
+    async fn record_events_batch(&self, events: &[(Event, String)]) -> Result<Vec<String>>;
+    async fn link_event_to_feature(&self, event_id: &str, feature_id: &str) -> Result<()>;
+    /// Link many (event_id, feature_id) pairs in one round trip.
+    async fn link_events_to_features_batch(&self, links: &[(String, String)]) -> Result<()>;
+    /// List events matching `filter`, newest first, returning up to
+    /// `filter.limit` results plus a cursor for the next page.
+    async fn query_events(&self, filter: EventFilter) -> Result<EventPage>;
+    /// Count and success-rate events matching `filter`, bucketed by
+    /// `filter.group_by` (day, week, tool, or source agent). See
+    /// `AnalyticsFilter` for the full set of composable conditions.
+    async fn query_analytics(&self, filter: AnalyticsFilter) -> Result<Vec<AnalyticsBucket>>;
+
+    async fn start_session(&self, session_id: &str, agent: &str, project_path: &str) -> Result<()>;
+    async fn end_session(&self, session_id: &str) -> Result<()>;
+    async fn update_session_activity(&self, session_id: &str) -> Result<()>;
+    async fn get_active_sessions(&self, project_path: &str) -> Result<Vec<Session>>;
+    async fn get_all_sessions(&self, limit: i64) -> Result<Vec<Session>>;
+
+    async fn record_insight(&self, insight: &Insight, event_id: Option<&str>) -> Result<String>;
+    /// Record many insights in one round trip. Returns the generated ids in
+    /// the same order as `insights`.
+    async fn record_insights_batch(
+        &self,
+        insights: &[(Insight, Option<String>)],
+    ) -> Result<Vec<String>>;
+    async fn get_insights_by_tags(&self, tags: &[String], limit: i64) -> Result<Vec<Insight>>;
+    async fn get_insights_by_type(&self, pattern_type: &str, limit: i64) -> Result<Vec<Insight>>;
+    async fn increment_insight_usage(&self, insight_id: &str) -> Result<()>;
+    /// Record a positive/negative outcome signal for an insight and
+    /// recompute `effectiveness_score` from it. See `decayed_effectiveness_score`.
+    async fn record_insight_feedback(
+        &self,
+        insight_id: &str,
+        positive: bool,
+        half_life_days: Option<f64>,
+    ) -> Result<()>;
+    /// Fuzzy, ranked full-text search over insight descriptions and tags.
+    /// See `InsightSearchParams` for the scoring knobs.
+    async fn search_insights(&self, params: InsightSearchParams) -> Result<Vec<Insight>>;
+
+    async fn create_rule(&self, rule: &Rule, project_path: Option<&str>) -> Result<String>;
+    async fn get_rules_by_scope(&self, scope: &str, project_path: Option<&str>) -> Result<Vec<Rule>>;
+    async fn get_enabled_rules(&self, project_path: &str) -> Result<Vec<Rule>>;
+    async fn toggle_rule(&self, rule_id: &str, enabled: bool) -> Result<()>;
+    async fn increment_rule_triggered(&self, rule_id: &str) -> Result<()>;
+    /// Record a positive/negative outcome signal for a rule (e.g. "fired and
+    /// was accepted" vs "fired and was overridden") and recompute
+    /// `effectiveness_score` from it.
+    async fn record_rule_feedback(
+        &self,
+        rule_id: &str,
+        positive: bool,
+        half_life_days: Option<f64>,
+    ) -> Result<()>;
+    async fn link_rule_to_insight(&self, rule_id: &str, insight_id: &str) -> Result<()>;
+
+    /// Walk the provenance chain from `node_id` (a `Rule`, `Insight`,
+    /// `Event`, `Feature`, or `Session` id) toward its originating
+    /// `Project`, following `LINEAGE_RELATIONSHIPS` up to `max_depth` hops.
+    /// Returns an ordered edge list suitable for rendering an audit/
+    /// explanation view of why a given rule or insight exists.
+    async fn get_lineage(&self, node_id: &str, max_depth: i64) -> Result<Vec<LineageEdge>>;
+
+    async fn get_project_stats(&self, project_path: &str) -> Result<ProjectStats>;
+}
 
-        let mut events = Vec::new();
-        while let Some(row) = result.next().await? {
-            let payload_str: Option<String> = row.get("payload").ok();
-            let payload: Option<serde_json::Value> = payload_str
-                .and_then(|s| serde_json::from_str(&s).ok());
+/// Which `GraphStore` backend `build_store` should construct.
+#[derive(Debug, Clone, Default)]
+pub enum GraphBackend {
+    /// Memgraph as the source of truth, with the embedded SQLite store as a
+    /// read-through/failover cache in front of it (`LayeredStore`).
+    #[default]
+    Layered,
+    /// The embedded SQLite store alone, so ijoka can run without standing
+    /// up a Memgraph instance.
+    Local,
+}
 
-            events.push(Event {
-                id: row.get("id").ok(),
-                event_type: row.get("event_type")?,
-                tool_name: row.get("tool_name").ok(),
-                payload,
-                summary: row.get("summary").ok(),
-                timestamp: row.get("timestamp").ok(),
-                success: row.get("success").ok(),
-                source_agent: row.get("source_agent").ok(),
-                session_id: row.get("session_id").ok(),
-                project_path: row.get("project_path").ok(),
-                feature_id: Some(feature_id.to_string()),
-                feature_description: row.get("feature_description").ok(),
-            });
+/// Construct the configured `GraphStore` backend from `config`.
+pub fn build_store(backend: GraphBackend, config: GraphDbConfig) -> Result<Arc<dyn GraphStore>> {
+    match backend {
+        GraphBackend::Local => Ok(Arc::new(SqliteStore::open(&config.sqlite_path)?)),
+        GraphBackend::Layered => {
+            let cache = Arc::new(SqliteStore::open(&config.sqlite_path)?);
+            let graph = Arc::new(MemgraphStore::with_config(config));
+            Ok(Arc::new(LayeredStore::new(graph, cache)))
         }
-
-        Ok(events)
     }
+}
 
+#[async_trait]
+impl GraphStore for MemgraphStore {
     // =========================================================================
-    // SESSION OPERATIONS
+    // PROJECT OPERATIONS
     // =========================================================================
 
-    /// Start a new session
-    pub async fn start_session(
-        &self,
-        session_id: &str,
-        agent: &str,
-        project_path: &str,
-    ) -> Result<()> {
-        let graph = self.get_graph().await?;
+    /// Create or update a project
+    async fn upsert_project(&self, project: &Project) -> Result<()> {
+        self.instrument("upsert_project", async move {
+            const CYPHER: &str = r#"
+                MERGE (p:Project {path: $path})
+                ON CREATE SET
+                    p.id = $id,
+                    p.name = $name,
+                    p.description = $description,
+                    p.created_at = datetime(),
+                    p.updated_at = datetime(),
+                    p.settings = $settings
+                ON MATCH SET
+                    p.name = $name,
+                    p.description = $description,
+                    p.updated_at = datetime(),
+                    p.settings = $settings
+                "#;
+
+            self.execute_mutation(
+                CYPHER,
+                vec![
+                    ("id".into(), WalValue::Text(project.id.clone())),
+                    ("path".into(), WalValue::Text(project.path.clone())),
+                    ("name".into(), WalValue::Text(project.name.clone())),
+                    (
+                        "description".into(),
+                        WalValue::Text(project.description.clone().unwrap_or_default()),
+                    ),
+                    (
+                        "settings".into(),
+                        WalValue::Text(serde_json::to_string(&project.settings).unwrap_or_default()),
+                    ),
+                ],
+            )
+            .await
+        })
+        .await
+    }
 
-        let q = query(
-            r#"
-            MATCH (p:Project {path: $project_path})
-            CREATE (s:Session {
-                id: $id,
-                agent: $agent,
-                status: 'active',
-                started_at: datetime(),
-                last_activity: datetime(),
-                event_count: 0,
-                is_subagent: false
-            })-[:IN_PROJECT]->(p)
-            "#,
-        )
-        .param("project_path", project_path)
-        .param("id", session_id)
-        .param("agent", agent);
+    /// Get all projects
+    async fn get_projects(&self) -> Result<Vec<Project>> {
+        self.instrument("get_projects", async move {
+            let graph = self.get_graph().await?;
 
-        graph.run(q).await?;
-        Ok(())
-    }
+            let q = query("MATCH (p:Project) RETURN p ORDER BY p.name");
+            let mut result = graph.execute(q).await?;
 
-    /// End a session
-    pub async fn end_session(&self, session_id: &str) -> Result<()> {
-        let graph = self.get_graph().await?;
+            let mut projects = Vec::new();
+            while let Some(row) = result.next().await? {
+                let node: Node = row.get("p")?;
+                projects.push(Project::from_node(&node)?);
+            }
 
-        let q = query(
-            r#"
-            MATCH (s:Session {id: $id})
-            SET s.status = 'ended', s.ended_at = datetime()
-            "#,
-        )
-        .param("id", session_id);
+            Ok(projects)
+        })
+        .await
+    }
 
-        graph.run(q).await?;
-        Ok(())
+    /// Get project by path
+    async fn get_project_by_path(&self, path: &str) -> Result<Option<Project>> {
+        self.instrument("get_project_by_path", async move {
+            let graph = self.get_graph().await?;
+
+            let q = query("MATCH (p:Project {path: $path}) RETURN p").param("path", path);
+            let mut result = graph.execute(q).await?;
+
+            if let Some(row) = result.next().await? {
+                let node: Node = row.get("p")?;
+                Ok(Some(Project::from_node(&node)?))
+            } else {
+                Ok(None)
+            }
+        })
+        .await
     }
 
-    /// Update session activity
-    pub async fn update_session_activity(&self, session_id: &str) -> Result<()> {
-        let graph = self.get_graph().await?;
+    // =========================================================================
+    // FEATURE OPERATIONS
+    // =========================================================================
 
-        let q = query(
-            r#"
-            MATCH (s:Session {id: $id})
-            SET s.last_activity = datetime(), s.event_count = s.event_count + 1
-            "#,
-        )
-        .param("id", session_id);
+    /// Create a new feature linked to a project
+    async fn create_feature(&self, feature: &Feature, project_path: &str) -> Result<String> {
+        self.instrument("create_feature", async move {
+            const CYPHER: &str = r#"
+                MATCH (p:Project {path: $project_path})
+                CREATE (f:Feature {
+                    id: $id,
+                    description: $description,
+                    category: $category,
+                    status: $status,
+                    priority: $priority,
+                    steps: $steps,
+                    created_at: datetime(),
+                    updated_at: datetime(),
+                    work_count: 0
+                })-[:BELONGS_TO]->(p)
+                "#;
+
+            let feature_id = feature
+                .id
+                .clone()
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+            self.execute_mutation(
+                CYPHER,
+                vec![
+                    ("project_path".into(), WalValue::Text(project_path.to_string())),
+                    ("id".into(), WalValue::Text(feature_id.clone())),
+                    ("description".into(), WalValue::Text(feature.description.clone())),
+                    ("category".into(), WalValue::Text(feature.category.clone())),
+                    ("status".into(), WalValue::Text(feature.status.clone())),
+                    ("priority".into(), WalValue::Int(feature.priority.unwrap_or(0) as i64)),
+                    ("steps".into(), WalValue::List(feature.steps.clone().unwrap_or_default())),
+                ],
+            )
+            .await?;
 
-        graph.run(q).await?;
-        Ok(())
+            Ok(feature_id)
+        })
+        .await
     }
 
-    /// Get active sessions for a project
-    pub async fn get_active_sessions(&self, project_path: &str) -> Result<Vec<Session>> {
-        let graph = self.get_graph().await?;
+    /// Get all features for a project
+    async fn get_features_for_project(&self, project_path: &str) -> Result<Vec<Feature>> {
+        self.instrument("get_features_for_project", async move {
+            let graph = self.get_graph().await?;
 
-        let q = query(
-            r#"
-            MATCH (s:Session {status: 'active'})-[:IN_PROJECT]->(p:Project {path: $project_path})
-            RETURN s
-            ORDER BY s.last_activity DESC
-            "#,
-        )
-        .param("project_path", project_path);
+            let q = query(
+                r#"
+                MATCH (f:Feature)-[:BELONGS_TO]->(p:Project {path: $project_path})
+                RETURN f
+                ORDER BY f.priority DESC, f.created_at DESC
+                "#,
+            )
+            .param("project_path", project_path);
 
-        let mut result = graph.execute(q).await?;
+            let mut result = graph.execute(q).await?;
 
-        let mut sessions = Vec::new();
-        while let Some(row) = result.next().await? {
-            let node: Node = row.get("s")?;
-            sessions.push(Session::from_node(&node)?);
-        }
+            let mut features = Vec::new();
+            while let Some(row) = result.next().await? {
+                let node: Node = row.get("f")?;
+                let mut feature = Feature::from_node(&node)?;
+                feature.project_dir = Some(project_path.to_string());
+                features.push(feature);
+            }
 
-        Ok(sessions)
+            Ok(features)
+        })
+        .await
     }
 
-    /// Get all sessions (global view)
-    pub async fn get_all_sessions(&self, limit: i64) -> Result<Vec<Session>> {
-        let graph = self.get_graph().await?;
-
-        let q = query(
-            r#"
-            MATCH (s:Session)
-            RETURN s
-            ORDER BY s.last_activity DESC
-            LIMIT $limit
-            "#,
-        )
-        .param("limit", limit);
+    /// Get active feature for a project (status = 'in_progress')
+    async fn get_active_feature(&self, project_path: &str) -> Result<Option<Feature>> {
+        self.instrument("get_active_feature", async move {
+            let graph = self.get_graph().await?;
 
-        let mut result = graph.execute(q).await?;
+            let q = query(
+                r#"
+                MATCH (f:Feature {status: 'in_progress'})-[:BELONGS_TO]->(p:Project {path: $project_path})
+                RETURN f
+                LIMIT 1
+                "#,
+            )
+            .param("project_path", project_path);
 
-        let mut sessions = Vec::new();
-        while let Some(row) = result.next().await? {
-            let node: Node = row.get("s")?;
-            sessions.push(Session::from_node(&node)?);
-        }
+            let mut result = graph.execute(q).await?;
 
-        Ok(sessions)
+            if let Some(row) = result.next().await? {
+                let node: Node = row.get("f")?;
+                Ok(Some(Feature::from_node(&node)?))
+            } else {
+                Ok(None)
+            }
+        })
+        .await
     }
 
-    // =========================================================================
-    // INSIGHT OPERATIONS
-    // =========================================================================
-
-    /// Record a new insight
-    pub async fn record_insight(&self, insight: &Insight, event_id: Option<&str>) -> Result<String> {
-        let graph = self.get_graph().await?;
-
-        let insight_id = insight
-            .id
-            .clone()
-            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    /// Update feature status
+    async fn update_feature_status(&self, feature_id: &str, status: &str) -> Result<()> {
+        self.instrument("update_feature_status", async move {
+            const CYPHER: &str = r#"
+                MATCH (f:Feature {id: $id})
+                SET f.status = $status, f.updated_at = datetime()
+                "#;
+
+            self.execute_mutation(
+                CYPHER,
+                vec![
+                    ("id".into(), WalValue::Text(feature_id.to_string())),
+                    ("status".into(), WalValue::Text(status.to_string())),
+                ],
+            )
+            .await
+        })
+        .await
+    }
 
-        let q = query(
-            r#"
-            CREATE (i:Insight {
-                id: $id,
-                description: $description,
-                pattern_type: $pattern_type,
-                tags: $tags,
-                created_at: datetime(),
-                usage_count: 0,
-                effectiveness_score: $effectiveness_score
-            })
-            RETURN i.id as id
-            "#,
-        )
-        .param("id", insight_id.clone())
-        .param("description", insight.description.clone())
-        .param("pattern_type", insight.pattern_type.clone())
-        .param("tags", insight.tags.clone().unwrap_or_default())
-        .param("effectiveness_score", insight.effectiveness_score.unwrap_or(0.0));
+    /// Activate a feature (set to in_progress)
+    /// Multiple features can be in_progress simultaneously
+    async fn activate_feature(&self, _project_path: &str, feature_id: &str) -> Result<()> {
+        self.instrument("activate_feature", async move {
+            const CYPHER: &str = r#"
+                MATCH (f:Feature {id: $id})
+                SET f.status = 'in_progress', f.updated_at = datetime()
+                "#;
+
+            self.execute_mutation(CYPHER, vec![("id".into(), WalValue::Text(feature_id.to_string()))])
+                .await
+        })
+        .await
+    }
 
-        graph.run(q).await?;
+    /// Complete a feature
+    async fn complete_feature(&self, feature_id: &str) -> Result<()> {
+        self.instrument("complete_feature", async move {
+            const CYPHER: &str = r#"
+                MATCH (f:Feature {id: $id})
+                SET f.status = 'complete', f.completed_at = datetime(), f.updated_at = datetime()
+                "#;
+
+            self.execute_mutation(CYPHER, vec![("id".into(), WalValue::Text(feature_id.to_string()))])
+                .await
+        })
+        .await
+    }
 
-        // Link to source event if provided
-        if let Some(eid) = event_id {
-            let link_q = query(
+    /// Increment work count for a feature
+    ///
+    /// Requires a live connection: unlike the fire-and-forget mutations
+    /// above, the caller needs the resulting counter, so this can't be
+    /// deferred into the write-ahead queue.
+    async fn increment_work_count(&self, feature_id: &str) -> Result<i64> {
+        self.instrument("increment_work_count", async move {
+            let graph = self.get_graph().await?;
+
+            let q = query(
                 r#"
-                MATCH (i:Insight {id: $insight_id}), (e:Event {id: $event_id})
-                MERGE (i)-[:LEARNED_FROM]->(e)
+                MATCH (f:Feature {id: $id})
+                SET f.work_count = f.work_count + 1, f.updated_at = datetime()
+                RETURN f.work_count as count
                 "#,
             )
-            .param("insight_id", insight_id.clone())
-            .param("event_id", eid);
-            graph.run(link_q).await?;
-        }
+            .param("id", feature_id);
 
-        Ok(insight_id)
+            let mut result = graph.execute(q).await?;
+            if let Some(row) = result.next().await? {
+                Ok(row.get::<i64>("count")?)
+            } else {
+                Ok(0)
+            }
+        })
+        .await
     }
 
-    /// Get insights by tags
-    pub async fn get_insights_by_tags(&self, tags: &[String], limit: i64) -> Result<Vec<Insight>> {
-        let graph = self.get_graph().await?;
-
-        let q = query(
-            r#"
-            MATCH (i:Insight)
-            WHERE any(tag IN $tags WHERE tag IN i.tags)
-            RETURN i
-            ORDER BY i.usage_count DESC, i.created_at DESC
-            LIMIT $limit
-            "#,
-        )
-        .param("tags", tags.to_vec())
-        .param("limit", limit);
-
-        let mut result = graph.execute(q).await?;
+    // =========================================================================
+    // EVENT OPERATIONS
+    // =========================================================================
 
-        let mut insights = Vec::new();
-        while let Some(row) = result.next().await? {
-            let node: Node = row.get("i")?;
-            insights.push(Insight::from_node(&node)?);
-        }
+    /// Record an event
+    async fn record_event(&self, event: &Event, session_id: &str) -> Result<String> {
+        self.instrument("record_event", async move {
+            const CYPHER: &str = r#"
+                MATCH (s:Session {id: $session_id})
+                CREATE (e:Event {
+                    id: $id,
+                    event_type: $event_type,
+                    tool_name: $tool_name,
+                    payload: $payload,
+                    summary: $summary,
+                    timestamp: datetime(),
+                    success: $success
+                })-[:TRIGGERED_BY]->(s)
+                "#;
+
+            let event_id = event
+                .id
+                .clone()
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+            self.execute_mutation(
+                CYPHER,
+                vec![
+                    ("session_id".into(), WalValue::Text(session_id.to_string())),
+                    ("id".into(), WalValue::Text(event_id.clone())),
+                    ("event_type".into(), WalValue::Text(event.event_type.clone())),
+                    (
+                        "tool_name".into(),
+                        WalValue::Text(event.tool_name.clone().unwrap_or_default()),
+                    ),
+                    (
+                        "payload".into(),
+                        WalValue::Text(serde_json::to_string(&event.payload).unwrap_or_default()),
+                    ),
+                    (
+                        "summary".into(),
+                        WalValue::Text(event.summary.clone().unwrap_or_default()),
+                    ),
+                    ("success".into(), WalValue::Bool(event.success.unwrap_or(true))),
+                ],
+            )
+            .await?;
 
-        Ok(insights)
+            Ok(event_id)
+        })
+        .await
     }
 
-    /// Get insights by pattern type
-    pub async fn get_insights_by_type(&self, pattern_type: &str, limit: i64) -> Result<Vec<Insight>> {
-        let graph = self.get_graph().await?;
-
-        let q = query(
-            r#"
-            MATCH (i:Insight {pattern_type: $pattern_type})
-            RETURN i
-            ORDER BY i.usage_count DESC, i.created_at DESC
-            LIMIT $limit
-            "#,
-        )
-        .param("pattern_type", pattern_type)
-        .param("limit", limit);
+    /// Record many events in a single `UNWIND` statement instead of one
+    /// `CREATE` round trip per event, which matters because Bolt round-trip
+    /// cost dominates these tiny writes under a busy agent session.
+    async fn record_events_batch(&self, events: &[(Event, String)]) -> Result<Vec<String>> {
+        self.instrument("record_events_batch", async move {
+            const CYPHER: &str = r#"
+                UNWIND $events AS ev
+                MATCH (s:Session {id: ev.session_id})
+                CREATE (e:Event {
+                    id: ev.id,
+                    event_type: ev.event_type,
+                    tool_name: ev.tool_name,
+                    payload: ev.payload,
+                    summary: ev.summary,
+                    timestamp: datetime(),
+                    success: ev.success
+                })-[:TRIGGERED_BY]->(s)
+                "#;
+
+            if events.is_empty() {
+                return Ok(Vec::new());
+            }
 
-        let mut result = graph.execute(q).await?;
+            let mut ids = Vec::with_capacity(events.len());
+            let mut rows = Vec::with_capacity(events.len());
+            for (event, session_id) in events {
+                let event_id = event
+                    .id
+                    .clone()
+                    .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+                rows.push(vec![
+                    ("session_id".into(), WalValue::Text(session_id.clone())),
+                    ("id".into(), WalValue::Text(event_id.clone())),
+                    ("event_type".into(), WalValue::Text(event.event_type.clone())),
+                    (
+                        "tool_name".into(),
+                        WalValue::Text(event.tool_name.clone().unwrap_or_default()),
+                    ),
+                    (
+                        "payload".into(),
+                        WalValue::Text(serde_json::to_string(&event.payload).unwrap_or_default()),
+                    ),
+                    (
+                        "summary".into(),
+                        WalValue::Text(event.summary.clone().unwrap_or_default()),
+                    ),
+                    ("success".into(), WalValue::Bool(event.success.unwrap_or(true))),
+                ]);
+                ids.push(event_id);
+            }
 
-        let mut insights = Vec::new();
-        while let Some(row) = result.next().await? {
-            let node: Node = row.get("i")?;
-            insights.push(Insight::from_node(&node)?);
-        }
+            self.execute_mutation(CYPHER, vec![("events".into(), WalValue::Maps(rows))])
+                .await?;
 
-        Ok(insights)
+            Ok(ids)
+        })
+        .await
     }
 
-    /// Increment usage count for an insight
-    pub async fn increment_insight_usage(&self, insight_id: &str) -> Result<()> {
-        let graph = self.get_graph().await?;
+    /// Link an event to a feature
+    async fn link_event_to_feature(&self, event_id: &str, feature_id: &str) -> Result<()> {
+        self.instrument("link_event_to_feature", async move {
+            const CYPHER: &str = r#"
+                MATCH (e:Event {id: $event_id}), (f:Feature {id: $feature_id})
+                MERGE (e)-[:LINKED_TO]->(f)
+                "#;
+
+            self.execute_mutation(
+                CYPHER,
+                vec![
+                    ("event_id".into(), WalValue::Text(event_id.to_string())),
+                    ("feature_id".into(), WalValue::Text(feature_id.to_string())),
+                ],
+            )
+            .await
+        })
+        .await
+    }
 
-        let q = query(
-            r#"
-            MATCH (i:Insight {id: $id})
-            SET i.usage_count = i.usage_count + 1
-            "#,
-        )
-        .param("id", insight_id);
+    /// Link many events to features in a single `UNWIND` + `MERGE` instead
+    /// of one round trip per pair.
+    async fn link_events_to_features_batch(&self, links: &[(String, String)]) -> Result<()> {
+        self.instrument("link_events_to_features_batch", async move {
+            const CYPHER: &str = r#"
+                UNWIND $links AS link
+                MATCH (e:Event {id: link.event_id}), (f:Feature {id: link.feature_id})
+                MERGE (e)-[:LINKED_TO]->(f)
+                "#;
+
+            if links.is_empty() {
+                return Ok(());
+            }
 
-        graph.run(q).await?;
-        Ok(())
+            let rows = links
+                .iter()
+                .map(|(event_id, feature_id)| {
+                    vec![
+                        ("event_id".into(), WalValue::Text(event_id.clone())),
+                        ("feature_id".into(), WalValue::Text(feature_id.clone())),
+                    ]
+                })
+                .collect();
+
+            self.execute_mutation(CYPHER, vec![("links".into(), WalValue::Maps(rows))])
+                .await
+        })
+        .await
     }
 
-    /// Search insights by description
-    pub async fn search_insights(&self, search_term: &str, limit: i64) -> Result<Vec<Insight>> {
-        let graph = self.get_graph().await?;
+    /// List events matching `filter`, composing a dynamic `WHERE` clause
+    /// from whichever fields are set instead of hand-writing one query per
+    /// shape. Fetches one row past `filter.limit` to know whether a further
+    /// page exists, then trims it off before returning the cursor.
+    async fn query_events(&self, filter: EventFilter) -> Result<EventPage> {
+        self.instrument("query_events", async move {
+            let graph = self.get_graph().await?;
 
-        let q = query(
-            r#"
-            MATCH (i:Insight)
-            WHERE i.description CONTAINS $search_term
-            RETURN i
-            ORDER BY i.usage_count DESC
-            LIMIT $limit
-            "#,
-        )
-        .param("search_term", search_term)
-        .param("limit", limit);
+            let mut conditions = Vec::new();
+            let mut params: Vec<(String, WalValue)> = Vec::new();
 
-        let mut result = graph.execute(q).await?;
+            if let Some(project_path) = &filter.project_path {
+                conditions.push("p.path = $project_path".to_string());
+                params.push(("project_path".into(), WalValue::Text(project_path.clone())));
+            }
+            if let Some(feature_id) = &filter.feature_id {
+                conditions.push("f.id = $feature_id".to_string());
+                params.push(("feature_id".into(), WalValue::Text(feature_id.clone())));
+            }
+            if let Some(session_id) = &filter.session_id {
+                conditions.push("s.id = $session_id".to_string());
+                params.push(("session_id".into(), WalValue::Text(session_id.clone())));
+            }
+            if let Some(event_type) = &filter.event_type {
+                conditions.push("e.event_type = $event_type".to_string());
+                params.push(("event_type".into(), WalValue::Text(event_type.clone())));
+            }
+            if let Some(tool_name) = &filter.tool_name {
+                conditions.push("e.tool_name = $tool_name".to_string());
+                params.push(("tool_name".into(), WalValue::Text(tool_name.clone())));
+            }
+            if let Some(source_agent) = &filter.source_agent {
+                conditions.push("e.source_agent = $source_agent".to_string());
+                params.push(("source_agent".into(), WalValue::Text(source_agent.clone())));
+            }
+            if let Some(success) = filter.success {
+                conditions.push("e.success = $success".to_string());
+                params.push(("success".into(), WalValue::Bool(success)));
+            }
+            if let Some(after) = &filter.after {
+                conditions.push("e.timestamp >= $after".to_string());
+                params.push(("after".into(), WalValue::Text(after.clone())));
+            }
+            if let Some(before) = &filter.before {
+                conditions.push("e.timestamp < $before".to_string());
+                params.push(("before".into(), WalValue::Text(before.clone())));
+            }
+            if let Some(cursor) = &filter.cursor {
+                conditions.push(
+                    "(e.timestamp < $cursor_ts OR (e.timestamp = $cursor_ts AND e.id < $cursor_id))"
+                        .to_string(),
+                );
+                params.push(("cursor_ts".into(), WalValue::Text(cursor.timestamp.clone())));
+                params.push(("cursor_id".into(), WalValue::Text(cursor.id.clone())));
+            }
 
-        let mut insights = Vec::new();
-        while let Some(row) = result.next().await? {
-            let node: Node = row.get("i")?;
-            insights.push(Insight::from_node(&node)?);
-        }
+            let where_clause = if conditions.is_empty() {
+                String::new()
+            } else {
+                format!("WHERE {}", conditions.join(" AND "))
+            };
+            tracing::Span::current().record("param_count", params.len());
 
-        Ok(insights)
-    }
+            // Fetch one extra row so we know whether a further page exists.
+            params.push(("limit".into(), WalValue::Int(filter.limit + 1)));
 
-    // =========================================================================
-    // RULE OPERATIONS
-    // =========================================================================
+            let cypher = format!(
+                r#"
+                MATCH (e:Event)
+                OPTIONAL MATCH (e)-[:TRIGGERED_BY]->(s:Session)-[:IN_PROJECT]->(p:Project)
+                OPTIONAL MATCH (e)-[:LINKED_TO]->(f:Feature)
+                {where_clause}
+                RETURN e.id as id,
+                       e.event_type as event_type,
+                       e.tool_name as tool_name,
+                       e.payload as payload,
+                       e.summary as summary,
+                       toString(e.timestamp) as timestamp,
+                       e.success as success,
+                       e.source_agent as source_agent,
+                       s.id as session_id,
+                       p.path as project_path,
+                       f.id as feature_id,
+                       f.description as feature_description
+                ORDER BY e.timestamp DESC, e.id DESC
+                LIMIT $limit
+                "#
+            );
+
+            let q = rebuild_query(&cypher, &params);
+            let mut result = graph.execute(q).await?;
+
+            let mut events = Vec::new();
+            while let Some(row) = result.next().await? {
+                let payload_str: Option<String> = row.get("payload").ok();
+                let payload: Option<serde_json::Value> = payload_str
+                    .and_then(|s| serde_json::from_str(&s).ok());
+
+                events.push(Event {
+                    id: row.get("id").ok(),
+                    event_type: row.get("event_type")?,
+                    tool_name: row.get("tool_name").ok(),
+                    payload,
+                    summary: row.get("summary").ok(),
+                    timestamp: row.get("timestamp").ok(),
+                    success: row.get("success").ok(),
+                    source_agent: row.get("source_agent").ok(),
+                    session_id: row.get("session_id").ok(),
+                    project_path: row.get("project_path").ok(),
+                    feature_id: row.get("feature_id").ok(),
+                    feature_description: row.get("feature_description").ok(),
+                });
+            }
 
-    /// Create a new rule
-    pub async fn create_rule(&self, rule: &Rule, project_path: Option<&str>) -> Result<String> {
-        let graph = self.get_graph().await?;
+            let next_cursor = next_cursor(&mut events, filter.limit);
+            Ok(EventPage { events, next_cursor })
+        })
+        .await
+    }
 
-        let rule_id = rule
-            .id
-            .clone()
-            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    async fn query_analytics(&self, filter: AnalyticsFilter) -> Result<Vec<AnalyticsBucket>> {
+        self.instrument("query_analytics", async move {
+            let graph = self.get_graph().await?;
 
-        let q = query(
-            r#"
-            CREATE (r:Rule {
-                id: $id,
-                name: $name,
-                description: $description,
-                trigger: $trigger,
-                action: $action,
-                scope: $scope,
-                enforcement: $enforcement,
-                enabled: $enabled,
-                created_at: datetime(),
-                triggered_count: 0,
-                source_instruction_count: $source_instruction_count
-            })
-            RETURN r.id as id
-            "#,
-        )
-        .param("id", rule_id.clone())
-        .param("name", rule.name.clone())
-        .param("description", rule.description.clone())
-        .param("trigger", serde_json::to_string(&rule.trigger).unwrap_or_default())
-        .param("action", serde_json::to_string(&rule.action).unwrap_or_default())
-        .param("scope", rule.scope.clone())
-        .param("enforcement", rule.enforcement.clone())
-        .param("enabled", rule.enabled.unwrap_or(true))
-        .param("source_instruction_count", rule.source_instruction_count.unwrap_or(0) as i64);
-
-        graph.run(q).await?;
-
-        // Link to project if project-scoped
-        if let Some(path) = project_path {
-            if rule.scope == "project" {
-                let link_q = query(
-                    r#"
-                    MATCH (r:Rule {id: $rule_id}), (p:Project {path: $project_path})
-                    MERGE (r)-[:APPLIES_TO]->(p)
-                    "#,
-                )
-                .param("rule_id", rule_id.clone())
-                .param("project_path", path);
-                graph.run(link_q).await?;
+            let mut conditions = Vec::new();
+            let mut params: Vec<(String, WalValue)> = Vec::new();
+
+            if let Some(project_path) = &filter.project_path {
+                conditions.push("p.path = $project_path".to_string());
+                params.push(("project_path".into(), WalValue::Text(project_path.clone())));
+            }
+            if let Some(event_type) = &filter.event_type {
+                conditions.push("e.event_type = $event_type".to_string());
+                params.push(("event_type".into(), WalValue::Text(event_type.clone())));
+            }
+            if let Some(tool_name) = &filter.tool_name {
+                conditions.push("e.tool_name = $tool_name".to_string());
+                params.push(("tool_name".into(), WalValue::Text(tool_name.clone())));
+            }
+            if let Some(source_agent) = &filter.source_agent {
+                conditions.push("e.source_agent = $source_agent".to_string());
+                params.push(("source_agent".into(), WalValue::Text(source_agent.clone())));
+            }
+            if let Some(success) = filter.success {
+                conditions.push("e.success = $success".to_string());
+                params.push(("success".into(), WalValue::Bool(success)));
+            }
+            if let Some(after) = &filter.after {
+                conditions.push("e.timestamp >= $after".to_string());
+                params.push(("after".into(), WalValue::Text(after.clone())));
+            }
+            if let Some(before) = &filter.before {
+                conditions.push("e.timestamp < $before".to_string());
+                params.push(("before".into(), WalValue::Text(before.clone())));
+            }
+            if let Some(feature_category) = &filter.feature_category {
+                conditions.push("f.category = $feature_category".to_string());
+                params.push((
+                    "feature_category".into(),
+                    WalValue::Text(feature_category.clone()),
+                ));
+            }
+            if let Some(feature_status) = &filter.feature_status {
+                conditions.push("f.status = $feature_status".to_string());
+                params.push(("feature_status".into(), WalValue::Text(feature_status.clone())));
+            }
+            if let Some(session_agent) = &filter.session_agent {
+                conditions.push("s.agent = $session_agent".to_string());
+                params.push(("session_agent".into(), WalValue::Text(session_agent.clone())));
             }
-        }
 
-        Ok(rule_id)
-    }
+            let where_clause = if conditions.is_empty() {
+                String::new()
+            } else {
+                format!("WHERE {}", conditions.join(" AND "))
+            };
+            tracing::Span::current().record("param_count", params.len());
 
-    /// Get rules by scope
-    pub async fn get_rules_by_scope(&self, scope: &str, project_path: Option<&str>) -> Result<Vec<Rule>> {
-        let graph = self.get_graph().await?;
+            params.push((
+                "group_by".into(),
+                WalValue::Text(filter.group_by.as_str().to_string()),
+            ));
 
-        let q = if scope == "project" && project_path.is_some() {
-            query(
+            let cypher = format!(
                 r#"
-                MATCH (r:Rule {scope: 'project'})-[:APPLIES_TO]->(p:Project {path: $project_path})
-                WHERE r.enabled = true
-                RETURN r
-                ORDER BY r.created_at DESC
+                MATCH (e:Event)
+                OPTIONAL MATCH (e)-[:TRIGGERED_BY]->(s:Session)-[:IN_PROJECT]->(p:Project)
+                OPTIONAL MATCH (e)-[:LINKED_TO]->(f:Feature)
+                {where_clause}
+                WITH e, CASE $group_by
+                    WHEN 'day' THEN left(toString(e.timestamp), 10)
+                    WHEN 'week' THEN toString(date(e.timestamp).year) + '-W' + toString(date(e.timestamp).week)
+                    WHEN 'tool' THEN coalesce(e.tool_name, 'unknown')
+                    WHEN 'agent' THEN coalesce(e.source_agent, 'unknown')
+                    ELSE 'unknown'
+                END as bucket_key
+                WITH bucket_key,
+                     count(e) as total,
+                     sum(CASE WHEN e.success THEN 1 ELSE 0 END) as successes
+                RETURN bucket_key as key, total, successes
+                ORDER BY key
+                "#
+            );
+
+            let q = rebuild_query(&cypher, &params);
+            let mut result = graph.execute(q).await?;
+
+            let mut buckets = Vec::new();
+            while let Some(row) = result.next().await? {
+                let total: i64 = row.get("total")?;
+                let successes: i64 = row.get("successes")?;
+                buckets.push(AnalyticsBucket {
+                    key: row.get("key")?,
+                    total,
+                    successes,
+                    success_rate: if total > 0 {
+                        successes as f64 / total as f64
+                    } else {
+                        0.0
+                    },
+                });
+            }
+
+            Ok(buckets)
+        })
+        .await
+    }
+
+    // =========================================================================
+    // SESSION OPERATIONS
+    // =========================================================================
+
+    /// Start a new session
+    async fn start_session(
+        &self,
+        session_id: &str,
+        agent: &str,
+        project_path: &str,
+    ) -> Result<()> {
+        self.instrument("start_session", async move {
+            const CYPHER: &str = r#"
+                MATCH (p:Project {path: $project_path})
+                CREATE (s:Session {
+                    id: $id,
+                    agent: $agent,
+                    status: 'active',
+                    started_at: datetime(),
+                    last_activity: datetime(),
+                    event_count: 0,
+                    is_subagent: false
+                })-[:IN_PROJECT]->(p)
+                "#;
+
+            self.execute_mutation(
+                CYPHER,
+                vec![
+                    ("project_path".into(), WalValue::Text(project_path.to_string())),
+                    ("id".into(), WalValue::Text(session_id.to_string())),
+                    ("agent".into(), WalValue::Text(agent.to_string())),
+                ],
+            )
+            .await
+        })
+        .await
+    }
+
+    /// End a session
+    async fn end_session(&self, session_id: &str) -> Result<()> {
+        self.instrument("end_session", async move {
+            const CYPHER: &str = r#"
+                MATCH (s:Session {id: $id})
+                SET s.status = 'ended', s.ended_at = datetime()
+                "#;
+
+            self.execute_mutation(CYPHER, vec![("id".into(), WalValue::Text(session_id.to_string()))])
+                .await
+        })
+        .await
+    }
+
+    /// Update session activity
+    async fn update_session_activity(&self, session_id: &str) -> Result<()> {
+        self.instrument("update_session_activity", async move {
+            const CYPHER: &str = r#"
+                MATCH (s:Session {id: $id})
+                SET s.last_activity = datetime(), s.event_count = s.event_count + 1
+                "#;
+
+            self.execute_mutation(CYPHER, vec![("id".into(), WalValue::Text(session_id.to_string()))])
+                .await
+        })
+        .await
+    }
+
+    /// Get active sessions for a project
+    async fn get_active_sessions(&self, project_path: &str) -> Result<Vec<Session>> {
+        self.instrument("get_active_sessions", async move {
+            let graph = self.get_graph().await?;
+
+            let q = query(
+                r#"
+                MATCH (s:Session {status: 'active'})-[:IN_PROJECT]->(p:Project {path: $project_path})
+                RETURN s
+                ORDER BY s.last_activity DESC
                 "#,
             )
-            .param("project_path", project_path.unwrap())
-        } else {
-            query(
+            .param("project_path", project_path);
+
+            let mut result = graph.execute(q).await?;
+
+            let mut sessions = Vec::new();
+            while let Some(row) = result.next().await? {
+                let node: Node = row.get("s")?;
+                sessions.push(Session::from_node(&node)?);
+            }
+
+            Ok(sessions)
+        })
+        .await
+    }
+
+    /// Get all sessions (global view)
+    async fn get_all_sessions(&self, limit: i64) -> Result<Vec<Session>> {
+        self.instrument("get_all_sessions", async move {
+            let graph = self.get_graph().await?;
+
+            let q = query(
                 r#"
-                MATCH (r:Rule {scope: $scope})
-                WHERE r.enabled = true
-                RETURN r
-                ORDER BY r.created_at DESC
+                MATCH (s:Session)
+                RETURN s
+                ORDER BY s.last_activity DESC
+                LIMIT $limit
                 "#,
             )
-            .param("scope", scope)
-        };
+            .param("limit", limit);
 
-        let mut result = graph.execute(q).await?;
+            let mut result = graph.execute(q).await?;
 
-        let mut rules = Vec::new();
-        while let Some(row) = result.next().await? {
-            let node: Node = row.get("r")?;
-            rules.push(Rule::from_node(&node)?);
-        }
+            let mut sessions = Vec::new();
+            while let Some(row) = result.next().await? {
+                let node: Node = row.get("s")?;
+                sessions.push(Session::from_node(&node)?);
+            }
 
-        Ok(rules)
+            Ok(sessions)
+        })
+        .await
     }
 
-    /// Get all enabled rules (global + project-specific)
-    pub async fn get_enabled_rules(&self, project_path: &str) -> Result<Vec<Rule>> {
-        let graph = self.get_graph().await?;
+    // =========================================================================
+    // INSIGHT OPERATIONS
+    // =========================================================================
 
-        let q = query(
-            r#"
-            MATCH (r:Rule)
-            WHERE r.enabled = true
-            AND (r.scope = 'global'
-                 OR (r.scope = 'project' AND EXISTS {
-                     MATCH (r)-[:APPLIES_TO]->(p:Project {path: $project_path})
-                 }))
-            RETURN r
-            ORDER BY r.scope, r.created_at DESC
-            "#,
-        )
-        .param("project_path", project_path);
+    /// Record a new insight
+    async fn record_insight(&self, insight: &Insight, event_id: Option<&str>) -> Result<String> {
+        self.instrument("record_insight", async move {
+            const CYPHER: &str = r#"
+                CREATE (i:Insight {
+                    id: $id,
+                    description: $description,
+                    pattern_type: $pattern_type,
+                    tags: $tags,
+                    created_at: datetime(),
+                    usage_count: 0,
+                    effectiveness_score: $effectiveness_score
+                })
+                "#;
+            const LINK_CYPHER: &str = r#"
+                MATCH (i:Insight {id: $insight_id}), (e:Event {id: $event_id})
+                MERGE (i)-[:LEARNED_FROM]->(e)
+                "#;
+
+            let insight_id = insight
+                .id
+                .clone()
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+            self.execute_mutation(
+                CYPHER,
+                vec![
+                    ("id".into(), WalValue::Text(insight_id.clone())),
+                    ("description".into(), WalValue::Text(insight.description.clone())),
+                    ("pattern_type".into(), WalValue::Text(insight.pattern_type.clone())),
+                    ("tags".into(), WalValue::List(insight.tags.clone().unwrap_or_default())),
+                    (
+                        "effectiveness_score".into(),
+                        WalValue::Float(insight.effectiveness_score.unwrap_or(0.0)),
+                    ),
+                ],
+            )
+            .await?;
+
+            // Link to source event if provided
+            if let Some(eid) = event_id {
+                self.execute_mutation(
+                    LINK_CYPHER,
+                    vec![
+                        ("insight_id".into(), WalValue::Text(insight_id.clone())),
+                        ("event_id".into(), WalValue::Text(eid.to_string())),
+                    ],
+                )
+                .await?;
+            }
+
+            Ok(insight_id)
+        })
+        .await
+    }
 
-        let mut result = graph.execute(q).await?;
+    /// Record many insights (and their optional `LEARNED_FROM` links) in two
+    /// `UNWIND` round trips instead of up to two per insight.
+    async fn record_insights_batch(
+        &self,
+        insights: &[(Insight, Option<String>)],
+    ) -> Result<Vec<String>> {
+        self.instrument("record_insights_batch", async move {
+            const CYPHER: &str = r#"
+                UNWIND $insights AS ins
+                CREATE (i:Insight {
+                    id: ins.id,
+                    description: ins.description,
+                    pattern_type: ins.pattern_type,
+                    tags: ins.tags,
+                    created_at: datetime(),
+                    usage_count: 0,
+                    effectiveness_score: ins.effectiveness_score
+                })
+                "#;
+            const LINK_CYPHER: &str = r#"
+                UNWIND $links AS link
+                MATCH (i:Insight {id: link.insight_id}), (e:Event {id: link.event_id})
+                MERGE (i)-[:LEARNED_FROM]->(e)
+                "#;
 
-        let mut rules = Vec::new();
-        while let Some(row) = result.next().await? {
-            let node: Node = row.get("r")?;
-            rules.push(Rule::from_node(&node)?);
-        }
+            if insights.is_empty() {
+                return Ok(Vec::new());
+            }
 
-        Ok(rules)
+            let mut ids = Vec::with_capacity(insights.len());
+            let mut rows = Vec::with_capacity(insights.len());
+            let mut links = Vec::new();
+            for (insight, event_id) in insights {
+                let insight_id = insight
+                    .id
+                    .clone()
+                    .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+                rows.push(vec![
+                    ("id".into(), WalValue::Text(insight_id.clone())),
+                    ("description".into(), WalValue::Text(insight.description.clone())),
+                    ("pattern_type".into(), WalValue::Text(insight.pattern_type.clone())),
+                    ("tags".into(), WalValue::List(insight.tags.clone().unwrap_or_default())),
+                    (
+                        "effectiveness_score".into(),
+                        WalValue::Float(insight.effectiveness_score.unwrap_or(0.0)),
+                    ),
+                ]);
+
+                if let Some(eid) = event_id {
+                    links.push(vec![
+                        ("insight_id".into(), WalValue::Text(insight_id.clone())),
+                        ("event_id".into(), WalValue::Text(eid.clone())),
+                    ]);
+                }
+
+                ids.push(insight_id);
+            }
+
+            self.execute_mutation(CYPHER, vec![("insights".into(), WalValue::Maps(rows))])
+                .await?;
+
+            if !links.is_empty() {
+                self.execute_mutation(LINK_CYPHER, vec![("links".into(), WalValue::Maps(links))])
+                    .await?;
+            }
+
+            Ok(ids)
+        })
+        .await
     }
 
-    /// Toggle rule enabled status
-    pub async fn toggle_rule(&self, rule_id: &str, enabled: bool) -> Result<()> {
-        let graph = self.get_graph().await?;
+    /// Get insights by tags
+    async fn get_insights_by_tags(&self, tags: &[String], limit: i64) -> Result<Vec<Insight>> {
+        self.instrument("get_insights_by_tags", async move {
+            let graph = self.get_graph().await?;
 
-        let q = query(
-            r#"
-            MATCH (r:Rule {id: $id})
-            SET r.enabled = $enabled
-            "#,
-        )
-        .param("id", rule_id)
-        .param("enabled", enabled);
+            let q = query(
+                r#"
+                MATCH (i:Insight)
+                WHERE any(tag IN $tags WHERE tag IN i.tags)
+                RETURN i
+                ORDER BY i.usage_count DESC, i.created_at DESC
+                LIMIT $limit
+                "#,
+            )
+            .param("tags", tags.to_vec())
+            .param("limit", limit);
 
-        graph.run(q).await?;
-        Ok(())
+            let mut result = graph.execute(q).await?;
+
+            let mut insights = Vec::new();
+            while let Some(row) = result.next().await? {
+                let node: Node = row.get("i")?;
+                insights.push(Insight::from_node(&node)?);
+            }
+
+            Ok(insights)
+        })
+        .await
+    }
+
+    /// Get insights by pattern type
+    async fn get_insights_by_type(&self, pattern_type: &str, limit: i64) -> Result<Vec<Insight>> {
+        self.instrument("get_insights_by_type", async move {
+            let graph = self.get_graph().await?;
+
+            let q = query(
+                r#"
+                MATCH (i:Insight {pattern_type: $pattern_type})
+                RETURN i
+                ORDER BY coalesce(i.effectiveness_score, 0) DESC, i.usage_count DESC, i.created_at DESC
+                LIMIT $limit
+                "#,
+            )
+            .param("pattern_type", pattern_type)
+            .param("limit", limit);
+
+            let mut result = graph.execute(q).await?;
+
+            let mut insights = Vec::new();
+            while let Some(row) = result.next().await? {
+                let node: Node = row.get("i")?;
+                insights.push(Insight::from_node(&node)?);
+            }
+
+            Ok(insights)
+        })
+        .await
+    }
+
+    /// Increment usage count for an insight
+    async fn increment_insight_usage(&self, insight_id: &str) -> Result<()> {
+        self.instrument("increment_insight_usage", async move {
+            const CYPHER: &str = r#"
+                MATCH (i:Insight {id: $id})
+                SET i.usage_count = i.usage_count + 1
+                "#;
+
+            self.execute_mutation(CYPHER, vec![("id".into(), WalValue::Text(insight_id.to_string()))])
+                .await
+        })
+        .await
+    }
+
+    /// Record a positive/negative outcome signal for an insight (e.g. "used
+    /// and helped" vs "used and didn't"), then recompute and persist
+    /// `effectiveness_score` as the Wilson lower-confidence-bound of the
+    /// acceptance rate so far, decayed over `half_life_days` (defaults to
+    /// `DEFAULT_HALF_LIFE_DAYS` when `None`). Needs a live connection, like
+    /// `increment_work_count`, since it reads the updated counters back
+    /// before deriving and writing the score.
+    async fn record_insight_feedback(
+        &self,
+        insight_id: &str,
+        positive: bool,
+        half_life_days: Option<f64>,
+    ) -> Result<()> {
+        self.instrument("record_insight_feedback", async move {
+            let graph = self.get_graph().await?;
+
+            let q = query(
+                r#"
+                MATCH (i:Insight {id: $id})
+                SET i.feedback_pos = coalesce(i.feedback_pos, 0) + $inc,
+                    i.feedback_total = coalesce(i.feedback_total, 0) + 1,
+                    i.feedback_last_at = datetime()
+                RETURN i.feedback_pos as pos, i.feedback_total as total
+                "#,
+            )
+            .param("id", insight_id)
+            .param("inc", if positive { 1 } else { 0 });
+
+            let mut result = graph.execute(q).await?;
+            let Some(row) = result.next().await? else {
+                return Ok(());
+            };
+            let pos: i64 = row.get("pos")?;
+            let total: i64 = row.get("total")?;
+            let score = decayed_effectiveness_score(
+                pos,
+                total,
+                0.0,
+                half_life_days.unwrap_or(DEFAULT_HALF_LIFE_DAYS),
+            );
+
+            self.execute_mutation(
+                "MATCH (i:Insight {id: $id}) SET i.effectiveness_score = $score",
+                vec![
+                    ("id".into(), WalValue::Text(insight_id.to_string())),
+                    ("score".into(), WalValue::Float(score)),
+                ],
+            )
+            .await
+        })
+        .await
+    }
+
+    /// Fuzzy, ranked full-text search over insight descriptions and tags.
+    /// Memgraph has no fuzzy/edit-distance operator, so this pulls a
+    /// bounded candidate pool ordered by usage and re-ranks it in process
+    /// via `rank_insights`.
+    async fn search_insights(&self, params: InsightSearchParams) -> Result<Vec<Insight>> {
+        self.instrument("search_insights", async move {
+            let graph = self.get_graph().await?;
+
+            let q = query(
+                r#"
+                MATCH (i:Insight)
+                RETURN i
+                ORDER BY i.usage_count DESC
+                LIMIT $pool_size
+                "#,
+            )
+            .param("pool_size", SEARCH_CANDIDATE_POOL);
+
+            let mut result = graph.execute(q).await?;
+
+            let mut candidates = Vec::new();
+            while let Some(row) = result.next().await? {
+                let node: Node = row.get("i")?;
+                candidates.push(Insight::from_node(&node)?);
+            }
+
+            Ok(rank_insights(candidates, &params))
+        })
+        .await
+    }
+
+    // =========================================================================
+    // RULE OPERATIONS
+    // =========================================================================
+
+    /// Create a new rule
+    async fn create_rule(&self, rule: &Rule, project_path: Option<&str>) -> Result<String> {
+        self.instrument("create_rule", async move {
+            const CYPHER: &str = r#"
+                CREATE (r:Rule {
+                    id: $id,
+                    name: $name,
+                    description: $description,
+                    trigger: $trigger,
+                    action: $action,
+                    scope: $scope,
+                    enforcement: $enforcement,
+                    enabled: $enabled,
+                    created_at: datetime(),
+                    triggered_count: 0,
+                    source_instruction_count: $source_instruction_count
+                })
+                "#;
+            const LINK_CYPHER: &str = r#"
+                MATCH (r:Rule {id: $rule_id}), (p:Project {path: $project_path})
+                MERGE (r)-[:APPLIES_TO]->(p)
+                "#;
+
+            let rule_id = rule
+                .id
+                .clone()
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+            let mut statements = vec![(
+                CYPHER,
+                vec![
+                    ("id".into(), WalValue::Text(rule_id.clone())),
+                    ("name".into(), WalValue::Text(rule.name.clone())),
+                    ("description".into(), WalValue::Text(rule.description.clone())),
+                    (
+                        "trigger".into(),
+                        WalValue::Text(serde_json::to_string(&rule.trigger).unwrap_or_default()),
+                    ),
+                    (
+                        "action".into(),
+                        WalValue::Text(serde_json::to_string(&rule.action).unwrap_or_default()),
+                    ),
+                    ("scope".into(), WalValue::Text(rule.scope.clone())),
+                    ("enforcement".into(), WalValue::Text(rule.enforcement.clone())),
+                    ("enabled".into(), WalValue::Bool(rule.enabled.unwrap_or(true))),
+                    (
+                        "source_instruction_count".into(),
+                        WalValue::Int(rule.source_instruction_count.unwrap_or(0) as i64),
+                    ),
+                ],
+            )];
+
+            // Link to project if project-scoped, in the same transaction so
+            // a rule never ends up created without its project link.
+            if let Some(path) = project_path {
+                if rule.scope == "project" {
+                    statements.push((
+                        LINK_CYPHER,
+                        vec![
+                            ("rule_id".into(), WalValue::Text(rule_id.clone())),
+                            ("project_path".into(), WalValue::Text(path.to_string())),
+                        ],
+                    ));
+                }
+            }
+
+            self.execute_txn(statements).await?;
+
+            Ok(rule_id)
+        })
+        .await
+    }
+
+    /// Get rules by scope
+    async fn get_rules_by_scope(&self, scope: &str, project_path: Option<&str>) -> Result<Vec<Rule>> {
+        self.instrument("get_rules_by_scope", async move {
+            let graph = self.get_graph().await?;
+
+            let q = if scope == "project" && project_path.is_some() {
+                query(
+                    r#"
+                    MATCH (r:Rule {scope: 'project'})-[:APPLIES_TO]->(p:Project {path: $project_path})
+                    WHERE r.enabled = true
+                    RETURN r
+                    ORDER BY r.created_at DESC
+                    "#,
+                )
+                .param("project_path", project_path.unwrap())
+            } else {
+                query(
+                    r#"
+                    MATCH (r:Rule {scope: $scope})
+                    WHERE r.enabled = true
+                    RETURN r
+                    ORDER BY r.created_at DESC
+                    "#,
+                )
+                .param("scope", scope)
+            };
+
+            let mut result = graph.execute(q).await?;
+
+            let mut rules = Vec::new();
+            while let Some(row) = result.next().await? {
+                let node: Node = row.get("r")?;
+                rules.push(Rule::from_node(&node)?);
+            }
+
+            Ok(rules)
+        })
+        .await
+    }
+
+    /// Get all enabled rules (global + project-specific)
+    async fn get_enabled_rules(&self, project_path: &str) -> Result<Vec<Rule>> {
+        self.instrument("get_enabled_rules", async move {
+            let graph = self.get_graph().await?;
+
+            let q = query(
+                r#"
+                MATCH (r:Rule)
+                WHERE r.enabled = true
+                AND (r.scope = 'global'
+                     OR (r.scope = 'project' AND EXISTS {
+                         MATCH (r)-[:APPLIES_TO]->(p:Project {path: $project_path})
+                     }))
+                RETURN r
+                ORDER BY r.scope, r.created_at DESC
+                "#,
+            )
+            .param("project_path", project_path);
+
+            let mut result = graph.execute(q).await?;
+
+            let mut rules = Vec::new();
+            while let Some(row) = result.next().await? {
+                let node: Node = row.get("r")?;
+                rules.push(Rule::from_node(&node)?);
+            }
+
+            Ok(rules)
+        })
+        .await
+    }
+
+    /// Toggle rule enabled status
+    async fn toggle_rule(&self, rule_id: &str, enabled: bool) -> Result<()> {
+        self.instrument("toggle_rule", async move {
+            const CYPHER: &str = r#"
+                MATCH (r:Rule {id: $id})
+                SET r.enabled = $enabled
+                "#;
+
+            self.execute_mutation(
+                CYPHER,
+                vec![
+                    ("id".into(), WalValue::Text(rule_id.to_string())),
+                    ("enabled".into(), WalValue::Bool(enabled)),
+                ],
+            )
+            .await
+        })
+        .await
     }
 
     /// Increment triggered count for a rule
-    pub async fn increment_rule_triggered(&self, rule_id: &str) -> Result<()> {
-        let graph = self.get_graph().await?;
+    async fn increment_rule_triggered(&self, rule_id: &str) -> Result<()> {
+        self.instrument("increment_rule_triggered", async move {
+            const CYPHER: &str = r#"
+                MATCH (r:Rule {id: $id})
+                SET r.triggered_count = r.triggered_count + 1
+                "#;
+
+            self.execute_mutation(CYPHER, vec![("id".into(), WalValue::Text(rule_id.to_string()))])
+                .await
+        })
+        .await
+    }
 
-        let q = query(
-            r#"
-            MATCH (r:Rule {id: $id})
-            SET r.triggered_count = r.triggered_count + 1
-            "#,
-        )
-        .param("id", rule_id);
+    /// Record a positive/negative outcome signal for a rule (e.g. "fired
+    /// and was accepted" vs "fired and was overridden"), then recompute and
+    /// persist `effectiveness_score` the same way as
+    /// `record_insight_feedback`.
+    async fn record_rule_feedback(
+        &self,
+        rule_id: &str,
+        positive: bool,
+        half_life_days: Option<f64>,
+    ) -> Result<()> {
+        self.instrument("record_rule_feedback", async move {
+            let graph = self.get_graph().await?;
 
-        graph.run(q).await?;
-        Ok(())
+            let q = query(
+                r#"
+                MATCH (r:Rule {id: $id})
+                SET r.feedback_pos = coalesce(r.feedback_pos, 0) + $inc,
+                    r.feedback_total = coalesce(r.feedback_total, 0) + 1,
+                    r.feedback_last_at = datetime()
+                RETURN r.feedback_pos as pos, r.feedback_total as total
+                "#,
+            )
+            .param("id", rule_id)
+            .param("inc", if positive { 1 } else { 0 });
+
+            let mut result = graph.execute(q).await?;
+            let Some(row) = result.next().await? else {
+                return Ok(());
+            };
+            let pos: i64 = row.get("pos")?;
+            let total: i64 = row.get("total")?;
+            let score = decayed_effectiveness_score(
+                pos,
+                total,
+                0.0,
+                half_life_days.unwrap_or(DEFAULT_HALF_LIFE_DAYS),
+            );
+
+            self.execute_mutation(
+                "MATCH (r:Rule {id: $id}) SET r.effectiveness_score = $score",
+                vec![
+                    ("id".into(), WalValue::Text(rule_id.to_string())),
+                    ("score".into(), WalValue::Float(score)),
+                ],
+            )
+            .await
+        })
+        .await
     }
 
     /// Link a rule to an insight it was derived from
-    pub async fn link_rule_to_insight(&self, rule_id: &str, insight_id: &str) -> Result<()> {
-        let graph = self.get_graph().await?;
+    async fn link_rule_to_insight(&self, rule_id: &str, insight_id: &str) -> Result<()> {
+        self.instrument("link_rule_to_insight", async move {
+            const CYPHER: &str = r#"
+                MATCH (r:Rule {id: $rule_id}), (i:Insight {id: $insight_id})
+                MERGE (r)-[:DERIVED_FROM]->(i)
+                "#;
+
+            self.execute_txn(vec![(
+                CYPHER,
+                vec![
+                    ("rule_id".into(), WalValue::Text(rule_id.to_string())),
+                    ("insight_id".into(), WalValue::Text(insight_id.to_string())),
+                ],
+            )])
+            .await
+        })
+        .await
+    }
 
-        let q = query(
-            r#"
-            MATCH (r:Rule {id: $rule_id}), (i:Insight {id: $insight_id})
-            MERGE (r)-[:DERIVED_FROM]->(i)
-            "#,
-        )
-        .param("rule_id", rule_id)
-        .param("insight_id", insight_id);
+    /// Breadth-first walk outward from `node_id`, one `LINEAGE_RELATIONSHIPS`
+    /// hop at a time, collecting every edge crossed. Stops at `max_depth` or
+    /// once a frontier produces no further hops.
+    async fn get_lineage(&self, node_id: &str, max_depth: i64) -> Result<Vec<LineageEdge>> {
+        self.instrument("get_lineage", async move {
+            let graph = self.get_graph().await?;
+
+            let mut edges = Vec::new();
+            let mut seen = std::collections::HashSet::new();
+            let mut frontier = vec![node_id.to_string()];
+            let mut depth = 0;
+
+            while depth < max_depth && !frontier.is_empty() {
+                let mut next_frontier = Vec::new();
+
+                for id in &frontier {
+                    let cypher = format!(
+                        r#"
+                        MATCH (n {{id: $id}})-[r:{LINEAGE_RELATIONSHIPS}]->(m)
+                        RETURN type(r) as relationship,
+                               labels(n)[0] as from_label,
+                               labels(m)[0] as to_label,
+                               m.id as to_id,
+                               coalesce(toString(n.created_at), toString(n.timestamp)) as timestamp
+                        "#
+                    );
+                    let q = query(&cypher).param("id", id.as_str());
+                    let mut result = graph.execute(q).await?;
+
+                    while let Some(row) = result.next().await? {
+                        let relationship: String = row.get("relationship")?;
+                        let to_id: String = row.get("to_id")?;
+
+                        if !seen.insert((id.clone(), relationship.clone(), to_id.clone())) {
+                            continue;
+                        }
+
+                        edges.push(LineageEdge {
+                            relationship,
+                            from_label: row.get("from_label")?,
+                            from_id: id.clone(),
+                            to_label: row.get("to_label")?,
+                            to_id: to_id.clone(),
+                            timestamp: row.get("timestamp").ok(),
+                        });
+                        next_frontier.push(to_id);
+                    }
+                }
+
+                frontier = next_frontier;
+                depth += 1;
+            }
 
-        graph.run(q).await?;
-        Ok(())
+            Ok(edges)
+        })
+        .await
     }
 
     // =========================================================================
@@ -993,46 +2949,49 @@ impl GraphDb {
     // =========================================================================
 
     /// Get project statistics
-    pub async fn get_project_stats(&self, project_path: &str) -> Result<ProjectStats> {
-        let graph = self.get_graph().await?;
-
-        let q = query(
-            r#"
-            MATCH (p:Project {path: $project_path})
-            OPTIONAL MATCH (f:Feature)-[:BELONGS_TO]->(p)
-            WITH p,
-                 count(f) as total,
-                 sum(CASE WHEN f.status = 'complete' THEN 1 ELSE 0 END) as completed,
-                 sum(CASE WHEN f.status = 'in_progress' THEN 1 ELSE 0 END) as in_progress
-            OPTIONAL MATCH (s:Session {status: 'active'})-[:IN_PROJECT]->(p)
-            WITH total, completed, in_progress, count(s) as active_sessions
-            RETURN total, completed, in_progress, active_sessions
-            "#,
-        )
-        .param("project_path", project_path);
-
-        let mut result = graph.execute(q).await?;
+    async fn get_project_stats(&self, project_path: &str) -> Result<ProjectStats> {
+        self.instrument("get_project_stats", async move {
+            let graph = self.get_graph().await?;
 
-        if let Some(row) = result.next().await? {
-            let total: i64 = row.get("total")?;
-            let completed: i64 = row.get("completed")?;
-            let in_progress: i64 = row.get("in_progress")?;
-            let active_sessions: i64 = row.get("active_sessions")?;
-
-            Ok(ProjectStats {
-                total: total as i32,
-                completed: completed as i32,
-                in_progress: in_progress as i32,
-                percentage: if total > 0 {
-                    (completed as f64 / total as f64 * 100.0) as i32
-                } else {
-                    0
-                },
-                active_sessions: active_sessions as i32,
-            })
-        } else {
-            Ok(ProjectStats::default())
-        }
+            let q = query(
+                r#"
+                MATCH (p:Project {path: $project_path})
+                OPTIONAL MATCH (f:Feature)-[:BELONGS_TO]->(p)
+                WITH p,
+                     count(f) as total,
+                     sum(CASE WHEN f.status = 'complete' THEN 1 ELSE 0 END) as completed,
+                     sum(CASE WHEN f.status = 'in_progress' THEN 1 ELSE 0 END) as in_progress
+                OPTIONAL MATCH (s:Session {status: 'active'})-[:IN_PROJECT]->(p)
+                WITH total, completed, in_progress, count(s) as active_sessions
+                RETURN total, completed, in_progress, active_sessions
+                "#,
+            )
+            .param("project_path", project_path);
+
+            let mut result = graph.execute(q).await?;
+
+            if let Some(row) = result.next().await? {
+                let total: i64 = row.get("total")?;
+                let completed: i64 = row.get("completed")?;
+                let in_progress: i64 = row.get("in_progress")?;
+                let active_sessions: i64 = row.get("active_sessions")?;
+
+                Ok(ProjectStats {
+                    total: total as i32,
+                    completed: completed as i32,
+                    in_progress: in_progress as i32,
+                    percentage: if total > 0 {
+                        (completed as f64 / total as f64 * 100.0) as i32
+                    } else {
+                        0
+                    },
+                    active_sessions: active_sessions as i32,
+                })
+            } else {
+                Ok(ProjectStats::default())
+            }
+        })
+        .await
     }
 }
 
@@ -1136,44 +3095,300 @@ pub struct Event {
     pub feature_description: Option<String>,
 }
 
-impl Event {
-    fn from_node(node: &Node) -> Result<Self> {
-        Ok(Self {
-            id: node.get("id").ok(),
-            event_type: node.get("event_type")?,
-            tool_name: node.get("tool_name").ok(),
-            payload: serde_json::from_str(&node.get::<String>("payload").unwrap_or_default()).ok(),
-            summary: node.get("summary").ok(),
-            timestamp: node.get::<String>("timestamp").ok(),
-            success: node.get("success").ok(),
-            source_agent: node.get("source_agent").ok(),
-            session_id: None, // Populated from Session relationship
-            // These are populated by the caller after from_node
-            project_path: None,
-            feature_id: None,
-            feature_description: None,
+/// Opaque keyset-pagination cursor encoding the last event a caller has
+/// seen, as `(timestamp, id)`. Paging with this instead of an offset lets
+/// `query_events` seek straight to the next row instead of paying
+/// `SKIP`'s linear rescan cost on a large history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventCursor {
+    pub timestamp: String,
+    pub id: String,
+}
+
+impl EventCursor {
+    /// Encode as an opaque token safe to hand back to API callers.
+    pub fn encode(&self) -> String {
+        format!("{}|{}", self.timestamp, self.id)
+    }
+
+    /// Decode a token produced by `encode`. Returns `None` for malformed input.
+    pub fn decode(token: &str) -> Option<Self> {
+        let (timestamp, id) = token.split_once('|')?;
+        Some(Self {
+            timestamp: timestamp.to_string(),
+            id: id.to_string(),
         })
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Session {
-    pub id: String,
-    pub agent: String,
-    pub status: String,
-    pub started_at: Option<String>,
-    pub ended_at: Option<String>,
-    pub last_activity: Option<String>,
-    pub event_count: Option<i32>,
-    pub is_subagent: Option<bool>,
+/// A page of events returned by `GraphStore::query_events`, along with the
+/// cursor to pass back in via `EventFilter::cursor` to fetch the next page
+/// (`None` once the history is exhausted).
+#[derive(Debug, Clone, Default)]
+pub struct EventPage {
+    pub events: Vec<Event>,
+    pub next_cursor: Option<EventCursor>,
 }
 
-impl Session {
-    fn from_node(node: &Node) -> Result<Self> {
-        Ok(Self {
-            id: node.get("id")?,
-            agent: node.get("agent")?,
+/// Composable filter for `GraphStore::query_events`, replacing the
+/// fixed-shape `get_recent_events` / `get_all_recent_events` /
+/// `get_events_by_feature` queries with a single dynamic one. Every field
+/// left unset is simply omitted from the generated `WHERE` clause.
+#[derive(Debug, Clone)]
+pub struct EventFilter {
+    pub project_path: Option<String>,
+    pub feature_id: Option<String>,
+    pub session_id: Option<String>,
+    pub event_type: Option<String>,
+    pub tool_name: Option<String>,
+    pub source_agent: Option<String>,
+    pub success: Option<bool>,
+    /// Inclusive lower bound on `e.timestamp`.
+    pub after: Option<String>,
+    /// Exclusive upper bound on `e.timestamp`.
+    pub before: Option<String>,
+    pub cursor: Option<EventCursor>,
+    pub limit: i64,
+}
+
+impl Default for EventFilter {
+    fn default() -> Self {
+        Self {
+            project_path: None,
+            feature_id: None,
+            session_id: None,
+            event_type: None,
+            tool_name: None,
+            source_agent: None,
+            success: None,
+            after: None,
+            before: None,
+            cursor: None,
+            limit: 50,
+        }
+    }
+}
+
+impl EventFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn project_path(mut self, project_path: impl Into<String>) -> Self {
+        self.project_path = Some(project_path.into());
+        self
+    }
+
+    pub fn feature_id(mut self, feature_id: impl Into<String>) -> Self {
+        self.feature_id = Some(feature_id.into());
+        self
+    }
+
+    pub fn session_id(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    pub fn event_type(mut self, event_type: impl Into<String>) -> Self {
+        self.event_type = Some(event_type.into());
+        self
+    }
+
+    pub fn tool_name(mut self, tool_name: impl Into<String>) -> Self {
+        self.tool_name = Some(tool_name.into());
+        self
+    }
+
+    pub fn source_agent(mut self, source_agent: impl Into<String>) -> Self {
+        self.source_agent = Some(source_agent.into());
+        self
+    }
+
+    pub fn success(mut self, success: bool) -> Self {
+        self.success = Some(success);
+        self
+    }
+
+    pub fn after(mut self, after: impl Into<String>) -> Self {
+        self.after = Some(after.into());
+        self
+    }
+
+    pub fn before(mut self, before: impl Into<String>) -> Self {
+        self.before = Some(before.into());
+        self
+    }
+
+    pub fn cursor(mut self, cursor: EventCursor) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+
+    pub fn limit(mut self, limit: i64) -> Self {
+        self.limit = limit;
+        self
+    }
+}
+
+/// Which dimension to bucket `GraphStore::query_analytics` results by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnalyticsGroupBy {
+    Day,
+    Week,
+    Tool,
+    Agent,
+}
+
+impl AnalyticsGroupBy {
+    fn as_str(self) -> &'static str {
+        match self {
+            AnalyticsGroupBy::Day => "day",
+            AnalyticsGroupBy::Week => "week",
+            AnalyticsGroupBy::Tool => "tool",
+            AnalyticsGroupBy::Agent => "agent",
+        }
+    }
+}
+
+/// One bucket of `GraphStore::query_analytics` results: the day/week/tool/
+/// agent key named by `AnalyticsFilter::group_by`, the event count in it,
+/// and the fraction of those events that succeeded.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyticsBucket {
+    pub key: String,
+    pub total: i64,
+    pub successes: i64,
+    pub success_rate: f64,
+}
+
+/// Composable filter for `GraphStore::query_analytics`, replacing the single
+/// fixed `get_project_stats` aggregate with arbitrary "tool failures per
+/// agent over the last 7 days"-style dashboards. Every field left unset is
+/// simply omitted from the generated `WHERE` clause, same as `EventFilter`.
+#[derive(Debug, Clone)]
+pub struct AnalyticsFilter {
+    pub group_by: AnalyticsGroupBy,
+    pub project_path: Option<String>,
+    pub event_type: Option<String>,
+    pub tool_name: Option<String>,
+    pub source_agent: Option<String>,
+    pub success: Option<bool>,
+    /// Inclusive lower bound on event timestamp.
+    pub after: Option<String>,
+    /// Exclusive upper bound on event timestamp.
+    pub before: Option<String>,
+    pub feature_category: Option<String>,
+    pub feature_status: Option<String>,
+    pub session_agent: Option<String>,
+}
+
+impl AnalyticsFilter {
+    pub fn new(group_by: AnalyticsGroupBy) -> Self {
+        Self {
+            group_by,
+            project_path: None,
+            event_type: None,
+            tool_name: None,
+            source_agent: None,
+            success: None,
+            after: None,
+            before: None,
+            feature_category: None,
+            feature_status: None,
+            session_agent: None,
+        }
+    }
+
+    pub fn project_path(mut self, project_path: impl Into<String>) -> Self {
+        self.project_path = Some(project_path.into());
+        self
+    }
+
+    pub fn event_type(mut self, event_type: impl Into<String>) -> Self {
+        self.event_type = Some(event_type.into());
+        self
+    }
+
+    pub fn tool_name(mut self, tool_name: impl Into<String>) -> Self {
+        self.tool_name = Some(tool_name.into());
+        self
+    }
+
+    pub fn source_agent(mut self, source_agent: impl Into<String>) -> Self {
+        self.source_agent = Some(source_agent.into());
+        self
+    }
+
+    pub fn success(mut self, success: bool) -> Self {
+        self.success = Some(success);
+        self
+    }
+
+    pub fn after(mut self, after: impl Into<String>) -> Self {
+        self.after = Some(after.into());
+        self
+    }
+
+    pub fn before(mut self, before: impl Into<String>) -> Self {
+        self.before = Some(before.into());
+        self
+    }
+
+    pub fn feature_category(mut self, feature_category: impl Into<String>) -> Self {
+        self.feature_category = Some(feature_category.into());
+        self
+    }
+
+    pub fn feature_status(mut self, feature_status: impl Into<String>) -> Self {
+        self.feature_status = Some(feature_status.into());
+        self
+    }
+
+    pub fn session_agent(mut self, session_agent: impl Into<String>) -> Self {
+        self.session_agent = Some(session_agent.into());
+        self
+    }
+}
+
+impl Event {
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Self {
+            id: node.get("id").ok(),
+            event_type: node.get("event_type")?,
+            tool_name: node.get("tool_name").ok(),
+            payload: serde_json::from_str(&node.get::<String>("payload").unwrap_or_default()).ok(),
+            summary: node.get("summary").ok(),
+            timestamp: node.get::<String>("timestamp").ok(),
+            success: node.get("success").ok(),
+            source_agent: node.get("source_agent").ok(),
+            session_id: None, // Populated from Session relationship
+            // These are populated by the caller after from_node
+            project_path: None,
+            feature_id: None,
+            feature_description: None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    pub id: String,
+    pub agent: String,
+    pub status: String,
+    pub started_at: Option<String>,
+    pub ended_at: Option<String>,
+    pub last_activity: Option<String>,
+    pub event_count: Option<i32>,
+    pub is_subagent: Option<bool>,
+}
+
+impl Session {
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Self {
+            id: node.get("id")?,
+            agent: node.get("agent")?,
             status: node.get("status")?,
             started_at: node.get::<String>("started_at").ok(),
             ended_at: node.get::<String>("ended_at").ok(),
@@ -1219,6 +3434,201 @@ impl Insight {
     }
 }
 
+/// Parameters for `GraphStore::search_insights`: the query term, the result
+/// limit, and the two scoring knobs a caller can tune -- how many character
+/// edits a token may differ by and still count as a fuzzy match, and how
+/// heavily textual relevance is weighted against the usage/effectiveness
+/// boost in the final ranking.
+#[derive(Debug, Clone)]
+pub struct InsightSearchParams {
+    pub term: String,
+    pub limit: i64,
+    /// Terms longer than `FUZZY_MIN_TERM_LEN` may match a candidate token
+    /// within this many edits. Terms at or below that length must match
+    /// exactly, or short words (e.g. "ci", "api") would fuzzy-match nearly
+    /// anything.
+    pub max_edit_distance: usize,
+    pub relevance_weight: f64,
+    pub usage_weight: f64,
+}
+
+impl InsightSearchParams {
+    pub fn new(term: impl Into<String>, limit: i64) -> Self {
+        Self {
+            term: term.into(),
+            limit,
+            max_edit_distance: 2,
+            relevance_weight: 1.0,
+            usage_weight: 1.0,
+        }
+    }
+
+    pub fn max_edit_distance(mut self, max_edit_distance: usize) -> Self {
+        self.max_edit_distance = max_edit_distance;
+        self
+    }
+
+    pub fn relevance_weight(mut self, relevance_weight: f64) -> Self {
+        self.relevance_weight = relevance_weight;
+        self
+    }
+
+    pub fn usage_weight(mut self, usage_weight: f64) -> Self {
+        self.usage_weight = usage_weight;
+        self
+    }
+}
+
+/// Terms this long or shorter must match a candidate token exactly; fuzzy
+/// (edit-distance) matching only kicks in above this length.
+const FUZZY_MIN_TERM_LEN: usize = 4;
+/// How many candidate insights each backend pulls before re-ranking in
+/// process. Bounds the cost of scoring while still giving the ranker enough
+/// of the corpus to find fuzzy/low-rank matches that a plain `usage_count`
+/// ordering would cut off.
+const SEARCH_CANDIDATE_POOL: i64 = 500;
+
+/// Default half-life, in days, for the recency decay in
+/// `decayed_effectiveness_score`.
+const DEFAULT_HALF_LIFE_DAYS: f64 = 30.0;
+/// z-score for a 95% confidence interval, used by `wilson_lower_bound`.
+const WILSON_Z: f64 = 1.96;
+
+/// Wilson lower confidence bound of the success proportion `pos / n` --
+/// the standard way to rank "percent positive" scores without letting a
+/// single early signal (e.g. 1/1) outrank a well-established one (e.g.
+/// 90/100). Returns a neutral `0.0` when there are no signals yet.
+fn wilson_lower_bound(pos: i64, n: i64) -> f64 {
+    if n <= 0 {
+        return 0.0;
+    }
+    let n = n as f64;
+    let p_hat = pos as f64 / n;
+    let z = WILSON_Z;
+    (p_hat + z * z / (2.0 * n) - z * ((p_hat * (1.0 - p_hat) + z * z / (4.0 * n)) / n).sqrt())
+        / (1.0 + z * z / n)
+}
+
+/// Exponential recency decay that halves every `half_life_days` of age.
+fn recency_decay(age_days: f64, half_life_days: f64) -> f64 {
+    if half_life_days <= 0.0 {
+        return if age_days <= 0.0 { 1.0 } else { 0.0 };
+    }
+    2f64.powf(-age_days / half_life_days)
+}
+
+/// Ranking score for an insight or rule: the Wilson lower bound of its
+/// `pos`-out-of-`n` feedback signals, discounted by how long ago the most
+/// recent signal (`age_days`) landed. Replaces the unbounded `usage_count`/
+/// `triggered_count` counters, which only ever grow and so let a
+/// once-popular, now-stale pattern dominate every ranking forever.
+fn decayed_effectiveness_score(pos: i64, n: i64, age_days: f64, half_life_days: f64) -> f64 {
+    wilson_lower_bound(pos, n) * recency_decay(age_days, half_life_days)
+}
+
+/// Split `text` into lowercase alphanumeric tokens, discarding punctuation
+/// and whitespace.
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_lowercase())
+        .collect()
+}
+
+/// Levenshtein edit distance between two strings, in characters.
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut prev_diag = row[0];
+        row[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let new_val = (row[j] + 1)
+                .min(row[j - 1] + 1)
+                .min(prev_diag + cost);
+            prev_diag = row[j];
+            row[j] = new_val;
+        }
+    }
+    row[b.len()]
+}
+
+/// Textual relevance of `candidate_tokens` against `query_tokens`: the sum of
+/// per-term match weights (1.0 for an exact token match, a distance-scaled
+/// weight for a fuzzy match within `max_edit_distance`) divided by the
+/// number of candidate tokens. This is term frequency over matched tokens --
+/// a short description that matches every query term outranks a long one
+/// that buries the same matches among unrelated words.
+fn relevance_score(query_tokens: &[String], candidate_tokens: &[String], max_edit_distance: usize) -> f64 {
+    if candidate_tokens.is_empty() {
+        return 0.0;
+    }
+
+    let mut matched_weight = 0.0;
+    for qt in query_tokens {
+        let mut best = 0.0f64;
+        for ct in candidate_tokens {
+            let weight = if qt == ct {
+                1.0
+            } else if qt.chars().count() > FUZZY_MIN_TERM_LEN {
+                let dist = levenshtein_distance(qt, ct);
+                if dist <= max_edit_distance {
+                    1.0 - dist as f64 / (max_edit_distance + 1) as f64
+                } else {
+                    0.0
+                }
+            } else {
+                0.0
+            };
+            if weight > best {
+                best = weight;
+            }
+        }
+        matched_weight += best;
+    }
+
+    matched_weight / candidate_tokens.len() as f64
+}
+
+/// Rank `candidates` against `params.term`, dropping anything with zero
+/// relevance, and truncate to `params.limit`.
+fn rank_insights(candidates: Vec<Insight>, params: &InsightSearchParams) -> Vec<Insight> {
+    let query_tokens = tokenize(&params.term);
+
+    let mut scored: Vec<(f64, Insight)> = candidates
+        .into_iter()
+        .filter_map(|insight| {
+            let mut tokens = tokenize(&insight.description);
+            if let Some(tags) = &insight.tags {
+                tokens.extend(tags.iter().flat_map(|t| tokenize(t)));
+            }
+
+            let relevance = relevance_score(&query_tokens, &tokens, params.max_edit_distance);
+            if relevance <= 0.0 {
+                return None;
+            }
+
+            // `effectiveness_score` is the time-decayed Wilson lower bound
+            // from `decayed_effectiveness_score`, already folding in
+            // acceptance rate and recency -- a better usage signal than the
+            // monotonically-growing `usage_count` it replaces here.
+            let usage_boost = insight.effectiveness_score.unwrap_or(0.0);
+            let score = params.relevance_weight * relevance + params.usage_weight * usage_boost;
+            Some((score, insight))
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+    scored
+        .into_iter()
+        .take(params.limit.max(0) as usize)
+        .map(|(_, insight)| insight)
+        .collect()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Rule {
     pub id: Option<String>,
@@ -1232,6 +3642,8 @@ pub struct Rule {
     pub created_at: Option<String>,
     pub triggered_count: Option<i32>,
     pub source_instruction_count: Option<i32>,
+    /// Time-decayed Wilson lower-bound score from `record_rule_feedback`.
+    pub effectiveness_score: Option<f64>,
 }
 
 impl Rule {
@@ -1250,22 +3662,1585 @@ impl Rule {
             created_at: node.get::<String>("created_at").ok(),
             triggered_count: node.get::<i64>("triggered_count").ok().map(|c| c as i32),
             source_instruction_count: node.get::<i64>("source_instruction_count").ok().map(|c| c as i32),
+            effectiveness_score: node.get::<f64>("effectiveness_score").ok(),
         })
     }
 }
 
+/// One hop in a `GraphStore::get_lineage` provenance walk: the relationship
+/// followed, the label/id it started from, the label/id of the node it led
+/// to, and the timestamp (`created_at`, else `timestamp`) of the node it
+/// started from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LineageEdge {
+    pub relationship: String,
+    pub from_label: String,
+    pub from_id: String,
+    pub to_label: String,
+    pub to_id: String,
+    pub timestamp: Option<String>,
+}
+
+/// Relationship types `get_lineage` follows, always from a more specific
+/// node toward the more general one it originated from (e.g. `Rule` to the
+/// `Insight` it's `DERIVED_FROM`), so a single outgoing walk from any node
+/// leads toward its `Project`.
+const LINEAGE_RELATIONSHIPS: &str =
+    "DERIVED_FROM|LEARNED_FROM|TRIGGERED_BY|LINKED_TO|BELONGS_TO|IN_PROJECT|APPLIES_TO";
+
 // =============================================================================
-// TESTS
+// SQLITE-BACKED STORE
 // =============================================================================
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// A `GraphStore` backed entirely by a local SQLite database, mirroring the
+/// Memgraph node shapes in plain tables. Used on its own for running the app
+/// without a graph server, and as the cache layer inside `LayeredStore`.
+pub struct SqliteStore {
+    conn: Mutex<Connection>,
+}
 
-    #[test]
-    fn test_default_config() {
-        let config = GraphDbConfig::default();
-        assert_eq!(config.uri, "bolt://localhost:7687");
-        assert_eq!(config.user, "ijoka");
+impl SqliteStore {
+    pub fn open(path: &str) -> Result<Self> {
+        let conn = Connection::open(path).context("Failed to open SQLite graph store")?;
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS projects (
+                id TEXT PRIMARY KEY,
+                path TEXT UNIQUE NOT NULL,
+                name TEXT NOT NULL,
+                description TEXT,
+                created_at TEXT,
+                updated_at TEXT,
+                settings TEXT
+            );
+
+            CREATE TABLE IF NOT EXISTS features (
+                id TEXT PRIMARY KEY,
+                project_path TEXT NOT NULL,
+                description TEXT NOT NULL,
+                category TEXT NOT NULL,
+                status TEXT NOT NULL,
+                priority INTEGER,
+                steps TEXT,
+                created_at TEXT,
+                updated_at TEXT,
+                completed_at TEXT,
+                work_count INTEGER DEFAULT 0,
+                assigned_agent TEXT
+            );
+
+            CREATE TABLE IF NOT EXISTS events (
+                id TEXT PRIMARY KEY,
+                session_id TEXT,
+                project_path TEXT,
+                event_type TEXT NOT NULL,
+                tool_name TEXT,
+                payload TEXT,
+                summary TEXT,
+                timestamp TEXT,
+                success INTEGER,
+                source_agent TEXT,
+                feature_id TEXT,
+                feature_description TEXT
+            );
+
+            CREATE TABLE IF NOT EXISTS sessions (
+                id TEXT PRIMARY KEY,
+                agent TEXT NOT NULL,
+                project_path TEXT NOT NULL,
+                status TEXT NOT NULL,
+                started_at TEXT,
+                ended_at TEXT,
+                last_activity TEXT,
+                event_count INTEGER DEFAULT 0,
+                is_subagent INTEGER DEFAULT 0
+            );
+
+            CREATE TABLE IF NOT EXISTS insights (
+                id TEXT PRIMARY KEY,
+                description TEXT NOT NULL,
+                pattern_type TEXT NOT NULL,
+                tags TEXT,
+                created_at TEXT,
+                usage_count INTEGER DEFAULT 0,
+                effectiveness_score REAL,
+                feedback_pos INTEGER DEFAULT 0,
+                feedback_total INTEGER DEFAULT 0,
+                feedback_last_at TEXT
+            );
+
+            CREATE TABLE IF NOT EXISTS rules (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                description TEXT NOT NULL,
+                trigger TEXT,
+                action TEXT,
+                scope TEXT NOT NULL,
+                enforcement TEXT NOT NULL,
+                enabled INTEGER DEFAULT 1,
+                created_at TEXT,
+                triggered_count INTEGER DEFAULT 0,
+                source_instruction_count INTEGER DEFAULT 0,
+                project_path TEXT,
+                effectiveness_score REAL,
+                feedback_pos INTEGER DEFAULT 0,
+                feedback_total INTEGER DEFAULT 0,
+                feedback_last_at TEXT
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_features_project ON features(project_path);
+            CREATE INDEX IF NOT EXISTS idx_events_project ON events(project_path);
+            CREATE INDEX IF NOT EXISTS idx_events_feature ON events(feature_id);
+            CREATE INDEX IF NOT EXISTS idx_sessions_project ON sessions(project_path);
+            CREATE INDEX IF NOT EXISTS idx_rules_project ON rules(project_path);
+            "#,
+        )?;
+
+        // Migration: add feedback/effectiveness columns to tables created
+        // before this scoring subsystem existed. SQLite has no `ADD COLUMN
+        // IF NOT EXISTS`, so try and ignore errors on already-migrated DBs.
+        let _ = conn.execute("ALTER TABLE insights ADD COLUMN feedback_pos INTEGER DEFAULT 0", []);
+        let _ = conn.execute("ALTER TABLE insights ADD COLUMN feedback_total INTEGER DEFAULT 0", []);
+        let _ = conn.execute("ALTER TABLE insights ADD COLUMN feedback_last_at TEXT", []);
+        let _ = conn.execute("ALTER TABLE rules ADD COLUMN effectiveness_score REAL", []);
+        let _ = conn.execute("ALTER TABLE rules ADD COLUMN feedback_pos INTEGER DEFAULT 0", []);
+        let _ = conn.execute("ALTER TABLE rules ADD COLUMN feedback_total INTEGER DEFAULT 0", []);
+        let _ = conn.execute("ALTER TABLE rules ADD COLUMN feedback_last_at TEXT", []);
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn row_to_project(row: &rusqlite::Row) -> rusqlite::Result<Project> {
+        let settings: Option<String> = row.get("settings")?;
+        Ok(Project {
+            id: row.get("id")?,
+            path: row.get("path")?,
+            name: row.get("name")?,
+            description: row.get("description")?,
+            created_at: row.get("created_at")?,
+            updated_at: row.get("updated_at")?,
+            settings: settings
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .unwrap_or_default(),
+        })
+    }
+
+    fn row_to_feature(row: &rusqlite::Row) -> rusqlite::Result<Feature> {
+        let status: String = row.get("status")?;
+        let steps: Option<String> = row.get("steps")?;
+        Ok(Feature {
+            id: row.get("id")?,
+            description: row.get("description")?,
+            category: row.get("category")?,
+            passes: status == "complete",
+            in_progress: status == "in_progress",
+            status,
+            priority: row.get("priority")?,
+            steps: steps.and_then(|s| serde_json::from_str(&s).ok()),
+            created_at: row.get("created_at")?,
+            updated_at: row.get("updated_at")?,
+            completed_at: row.get("completed_at")?,
+            work_count: row.get("work_count")?,
+            assigned_agent: row.get("assigned_agent")?,
+            project_dir: row.get("project_path")?,
+        })
+    }
+
+    fn row_to_event(row: &rusqlite::Row) -> rusqlite::Result<Event> {
+        let payload: Option<String> = row.get("payload")?;
+        Ok(Event {
+            id: row.get("id")?,
+            event_type: row.get("event_type")?,
+            tool_name: row.get("tool_name")?,
+            payload: payload.and_then(|p| serde_json::from_str(&p).ok()),
+            summary: row.get("summary")?,
+            timestamp: row.get("timestamp")?,
+            success: row.get("success")?,
+            source_agent: row.get("source_agent")?,
+            session_id: row.get("session_id")?,
+            project_path: row.get("project_path")?,
+            feature_id: row.get("feature_id")?,
+            feature_description: row.get("feature_description")?,
+        })
+    }
+
+    fn row_to_session(row: &rusqlite::Row) -> rusqlite::Result<Session> {
+        Ok(Session {
+            id: row.get("id")?,
+            agent: row.get("agent")?,
+            status: row.get("status")?,
+            started_at: row.get("started_at")?,
+            ended_at: row.get("ended_at")?,
+            last_activity: row.get("last_activity")?,
+            event_count: row.get("event_count")?,
+            is_subagent: row.get("is_subagent")?,
+        })
+    }
+
+    fn row_to_insight(row: &rusqlite::Row) -> rusqlite::Result<Insight> {
+        let tags: Option<String> = row.get("tags")?;
+        Ok(Insight {
+            id: row.get("id")?,
+            description: row.get("description")?,
+            pattern_type: row.get("pattern_type")?,
+            tags: tags.and_then(|t| serde_json::from_str(&t).ok()),
+            created_at: row.get("created_at")?,
+            usage_count: row.get("usage_count")?,
+            effectiveness_score: row.get("effectiveness_score")?,
+        })
+    }
+
+    fn row_to_rule(row: &rusqlite::Row) -> rusqlite::Result<Rule> {
+        let trigger: Option<String> = row.get("trigger")?;
+        let action: Option<String> = row.get("action")?;
+        Ok(Rule {
+            id: row.get("id")?,
+            name: row.get("name")?,
+            description: row.get("description")?,
+            trigger: trigger
+                .and_then(|t| serde_json::from_str(&t).ok())
+                .unwrap_or_default(),
+            action: action
+                .and_then(|a| serde_json::from_str(&a).ok())
+                .unwrap_or_default(),
+            scope: row.get("scope")?,
+            enforcement: row.get("enforcement")?,
+            enabled: row.get("enabled")?,
+            created_at: row.get("created_at")?,
+            triggered_count: row.get("triggered_count")?,
+            source_instruction_count: row.get("source_instruction_count")?,
+            effectiveness_score: row.get("effectiveness_score")?,
+        })
+    }
+}
+
+#[async_trait]
+impl GraphStore for SqliteStore {
+    async fn upsert_project(&self, project: &Project) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO projects (id, path, name, description, created_at, updated_at, settings)
+             VALUES (?1, ?2, ?3, ?4, COALESCE((SELECT created_at FROM projects WHERE path = ?2), datetime('now')), datetime('now'), ?5)
+             ON CONFLICT(path) DO UPDATE SET
+                name = excluded.name,
+                description = excluded.description,
+                updated_at = datetime('now'),
+                settings = excluded.settings",
+            params![
+                project.id,
+                project.path,
+                project.name,
+                project.description,
+                serde_json::to_string(&project.settings).unwrap_or_default(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    async fn get_projects(&self) -> Result<Vec<Project>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT * FROM projects ORDER BY name")?;
+        let projects = stmt
+            .query_map([], Self::row_to_project)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(projects)
+    }
+
+    async fn get_project_by_path(&self, path: &str) -> Result<Option<Project>> {
+        let conn = self.conn.lock().unwrap();
+        let project = conn
+            .query_row("SELECT * FROM projects WHERE path = ?1", params![path], Self::row_to_project)
+            .ok();
+        Ok(project)
+    }
+
+    async fn create_feature(&self, feature: &Feature, project_path: &str) -> Result<String> {
+        let feature_id = feature
+            .id
+            .clone()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO features (id, project_path, description, category, status, priority, steps, created_at, updated_at, work_count)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, datetime('now'), datetime('now'), 0)
+             ON CONFLICT(id) DO UPDATE SET
+                project_path = excluded.project_path,
+                description = excluded.description,
+                category = excluded.category,
+                status = excluded.status,
+                priority = excluded.priority,
+                steps = excluded.steps,
+                updated_at = datetime('now')",
+            params![
+                feature_id,
+                project_path,
+                feature.description,
+                feature.category,
+                feature.status,
+                feature.priority,
+                feature.steps.as_ref().map(|s| serde_json::to_string(s).unwrap_or_default()),
+            ],
+        )?;
+        Ok(feature_id)
+    }
+
+    async fn get_features_for_project(&self, project_path: &str) -> Result<Vec<Feature>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT * FROM features WHERE project_path = ?1 ORDER BY priority DESC, created_at DESC",
+        )?;
+        let features = stmt
+            .query_map(params![project_path], Self::row_to_feature)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(features)
+    }
+
+    async fn get_active_feature(&self, project_path: &str) -> Result<Option<Feature>> {
+        let conn = self.conn.lock().unwrap();
+        let feature = conn
+            .query_row(
+                "SELECT * FROM features WHERE project_path = ?1 AND status = 'in_progress' LIMIT 1",
+                params![project_path],
+                Self::row_to_feature,
+            )
+            .ok();
+        Ok(feature)
+    }
+
+    async fn update_feature_status(&self, feature_id: &str, status: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE features SET status = ?1, updated_at = datetime('now') WHERE id = ?2",
+            params![status, feature_id],
+        )?;
+        Ok(())
+    }
+
+    async fn activate_feature(&self, _project_path: &str, feature_id: &str) -> Result<()> {
+        self.update_feature_status(feature_id, "in_progress").await
+    }
+
+    async fn complete_feature(&self, feature_id: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE features SET status = 'complete', completed_at = datetime('now'), updated_at = datetime('now') WHERE id = ?1",
+            params![feature_id],
+        )?;
+        Ok(())
+    }
+
+    async fn increment_work_count(&self, feature_id: &str) -> Result<i64> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE features SET work_count = work_count + 1, updated_at = datetime('now') WHERE id = ?1",
+            params![feature_id],
+        )?;
+        let count: i64 = conn.query_row(
+            "SELECT work_count FROM features WHERE id = ?1",
+            params![feature_id],
+            |r| r.get(0),
+        )?;
+        Ok(count)
+    }
+
+    async fn record_event(&self, event: &Event, session_id: &str) -> Result<String> {
+        let event_id = event
+            .id
+            .clone()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO events (id, session_id, project_path, event_type, tool_name, payload, summary, timestamp, success, source_agent, feature_id, feature_description)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, datetime('now'), ?8, ?9, ?10, ?11)
+             ON CONFLICT(id) DO UPDATE SET
+                session_id = excluded.session_id,
+                project_path = excluded.project_path,
+                event_type = excluded.event_type,
+                tool_name = excluded.tool_name,
+                payload = excluded.payload,
+                summary = excluded.summary,
+                success = excluded.success,
+                source_agent = excluded.source_agent,
+                feature_id = excluded.feature_id,
+                feature_description = excluded.feature_description",
+            params![
+                event_id,
+                session_id,
+                event.project_path,
+                event.event_type,
+                event.tool_name,
+                event.payload.as_ref().map(|p| p.to_string()),
+                event.summary,
+                event.success.unwrap_or(true),
+                event.source_agent,
+                event.feature_id,
+                event.feature_description,
+            ],
+        )?;
+        Ok(event_id)
+    }
+
+    async fn record_events_batch(&self, events: &[(Event, String)]) -> Result<Vec<String>> {
+        if events.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let mut ids = Vec::with_capacity(events.len());
+        for (event, session_id) in events {
+            let event_id = event
+                .id
+                .clone()
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+            tx.execute(
+                "INSERT INTO events (id, session_id, project_path, event_type, tool_name, payload, summary, timestamp, success, source_agent, feature_id, feature_description)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, datetime('now'), ?8, ?9, ?10, ?11)
+                 ON CONFLICT(id) DO UPDATE SET
+                    session_id = excluded.session_id,
+                    project_path = excluded.project_path,
+                    event_type = excluded.event_type,
+                    tool_name = excluded.tool_name,
+                    payload = excluded.payload,
+                    summary = excluded.summary,
+                    success = excluded.success,
+                    source_agent = excluded.source_agent,
+                    feature_id = excluded.feature_id,
+                    feature_description = excluded.feature_description",
+                params![
+                    event_id,
+                    session_id,
+                    event.project_path,
+                    event.event_type,
+                    event.tool_name,
+                    event.payload.as_ref().map(|p| p.to_string()),
+                    event.summary,
+                    event.success.unwrap_or(true),
+                    event.source_agent,
+                    event.feature_id,
+                    event.feature_description,
+                ],
+            )?;
+            ids.push(event_id);
+        }
+        tx.commit()?;
+
+        Ok(ids)
+    }
+
+    async fn link_event_to_feature(&self, event_id: &str, feature_id: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE events SET feature_id = ?1 WHERE id = ?2",
+            params![feature_id, event_id],
+        )?;
+        Ok(())
+    }
+
+    async fn link_events_to_features_batch(&self, links: &[(String, String)]) -> Result<()> {
+        if links.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        for (event_id, feature_id) in links {
+            tx.execute(
+                "UPDATE events SET feature_id = ?1 WHERE id = ?2",
+                params![feature_id, event_id],
+            )?;
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    async fn query_events(&self, filter: EventFilter) -> Result<EventPage> {
+        let mut conditions: Vec<String> = Vec::new();
+        let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+        if let Some(project_path) = &filter.project_path {
+            conditions.push("project_path = ?".to_string());
+            params.push(Box::new(project_path.clone()));
+        }
+        if let Some(feature_id) = &filter.feature_id {
+            conditions.push("feature_id = ?".to_string());
+            params.push(Box::new(feature_id.clone()));
+        }
+        if let Some(session_id) = &filter.session_id {
+            conditions.push("session_id = ?".to_string());
+            params.push(Box::new(session_id.clone()));
+        }
+        if let Some(event_type) = &filter.event_type {
+            conditions.push("event_type = ?".to_string());
+            params.push(Box::new(event_type.clone()));
+        }
+        if let Some(tool_name) = &filter.tool_name {
+            conditions.push("tool_name = ?".to_string());
+            params.push(Box::new(tool_name.clone()));
+        }
+        if let Some(source_agent) = &filter.source_agent {
+            conditions.push("source_agent = ?".to_string());
+            params.push(Box::new(source_agent.clone()));
+        }
+        if let Some(success) = filter.success {
+            conditions.push("success = ?".to_string());
+            params.push(Box::new(success));
+        }
+        if let Some(after) = &filter.after {
+            conditions.push("timestamp >= ?".to_string());
+            params.push(Box::new(after.clone()));
+        }
+        if let Some(before) = &filter.before {
+            conditions.push("timestamp < ?".to_string());
+            params.push(Box::new(before.clone()));
+        }
+        if let Some(cursor) = &filter.cursor {
+            conditions.push("(timestamp < ? OR (timestamp = ? AND id < ?))".to_string());
+            params.push(Box::new(cursor.timestamp.clone()));
+            params.push(Box::new(cursor.timestamp.clone()));
+            params.push(Box::new(cursor.id.clone()));
+        }
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+
+        // Fetch one extra row so we know whether a further page exists.
+        params.push(Box::new(filter.limit + 1));
+
+        let sql =
+            format!("SELECT * FROM events {where_clause} ORDER BY timestamp DESC, id DESC LIMIT ?");
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&sql)?;
+        let param_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+        let mut events = stmt
+            .query_map(param_refs.as_slice(), Self::row_to_event)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let next_cursor = next_cursor(&mut events, filter.limit);
+        Ok(EventPage { events, next_cursor })
+    }
+
+    async fn query_analytics(&self, filter: AnalyticsFilter) -> Result<Vec<AnalyticsBucket>> {
+        let mut conditions: Vec<String> = Vec::new();
+        let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+        if let Some(project_path) = &filter.project_path {
+            conditions.push("e.project_path = ?".to_string());
+            params.push(Box::new(project_path.clone()));
+        }
+        if let Some(event_type) = &filter.event_type {
+            conditions.push("e.event_type = ?".to_string());
+            params.push(Box::new(event_type.clone()));
+        }
+        if let Some(tool_name) = &filter.tool_name {
+            conditions.push("e.tool_name = ?".to_string());
+            params.push(Box::new(tool_name.clone()));
+        }
+        if let Some(source_agent) = &filter.source_agent {
+            conditions.push("e.source_agent = ?".to_string());
+            params.push(Box::new(source_agent.clone()));
+        }
+        if let Some(success) = filter.success {
+            conditions.push("e.success = ?".to_string());
+            params.push(Box::new(success));
+        }
+        if let Some(after) = &filter.after {
+            conditions.push("e.timestamp >= ?".to_string());
+            params.push(Box::new(after.clone()));
+        }
+        if let Some(before) = &filter.before {
+            conditions.push("e.timestamp < ?".to_string());
+            params.push(Box::new(before.clone()));
+        }
+        if let Some(feature_category) = &filter.feature_category {
+            conditions.push("f.category = ?".to_string());
+            params.push(Box::new(feature_category.clone()));
+        }
+        if let Some(feature_status) = &filter.feature_status {
+            conditions.push("f.status = ?".to_string());
+            params.push(Box::new(feature_status.clone()));
+        }
+        if let Some(session_agent) = &filter.session_agent {
+            conditions.push("s.agent = ?".to_string());
+            params.push(Box::new(session_agent.clone()));
+        }
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+
+        let sql = format!(
+            r#"
+            SELECT
+                CASE ?
+                    WHEN 'day' THEN substr(e.timestamp, 1, 10)
+                    WHEN 'week' THEN strftime('%Y-W%W', e.timestamp)
+                    WHEN 'tool' THEN coalesce(e.tool_name, 'unknown')
+                    WHEN 'agent' THEN coalesce(e.source_agent, 'unknown')
+                    ELSE 'unknown'
+                END as bucket_key,
+                COUNT(*) as total,
+                SUM(CASE WHEN e.success = 1 THEN 1 ELSE 0 END) as successes
+            FROM events e
+            LEFT JOIN features f ON e.feature_id = f.id
+            LEFT JOIN sessions s ON e.session_id = s.id
+            {where_clause}
+            GROUP BY bucket_key
+            ORDER BY bucket_key
+            "#
+        );
+
+        let group_by: Box<dyn rusqlite::ToSql> = Box::new(filter.group_by.as_str());
+        let mut bound_params: Vec<Box<dyn rusqlite::ToSql>> = vec![group_by];
+        bound_params.extend(params);
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&sql)?;
+        let param_refs: Vec<&dyn rusqlite::ToSql> = bound_params.iter().map(|p| p.as_ref()).collect();
+        let buckets = stmt
+            .query_map(param_refs.as_slice(), |row| {
+                let total: i64 = row.get("total")?;
+                let successes: i64 = row.get("successes")?;
+                Ok(AnalyticsBucket {
+                    key: row.get("bucket_key")?,
+                    total,
+                    successes,
+                    success_rate: if total > 0 {
+                        successes as f64 / total as f64
+                    } else {
+                        0.0
+                    },
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(buckets)
+    }
+
+    async fn start_session(&self, session_id: &str, agent: &str, project_path: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO sessions (id, agent, project_path, status, started_at, last_activity, event_count, is_subagent)
+             VALUES (?1, ?2, ?3, 'active', datetime('now'), datetime('now'), 0, 0)
+             ON CONFLICT(id) DO UPDATE SET
+                agent = excluded.agent,
+                project_path = excluded.project_path,
+                status = 'active',
+                last_activity = datetime('now')",
+            params![session_id, agent, project_path],
+        )?;
+        Ok(())
+    }
+
+    async fn end_session(&self, session_id: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE sessions SET status = 'ended', ended_at = datetime('now') WHERE id = ?1",
+            params![session_id],
+        )?;
+        Ok(())
+    }
+
+    async fn update_session_activity(&self, session_id: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE sessions SET last_activity = datetime('now'), event_count = event_count + 1 WHERE id = ?1",
+            params![session_id],
+        )?;
+        Ok(())
+    }
+
+    async fn get_active_sessions(&self, project_path: &str) -> Result<Vec<Session>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT * FROM sessions WHERE project_path = ?1 AND status = 'active' ORDER BY last_activity DESC",
+        )?;
+        let sessions = stmt
+            .query_map(params![project_path], Self::row_to_session)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(sessions)
+    }
+
+    async fn get_all_sessions(&self, limit: i64) -> Result<Vec<Session>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT * FROM sessions ORDER BY last_activity DESC LIMIT ?1")?;
+        let sessions = stmt
+            .query_map(params![limit], Self::row_to_session)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(sessions)
+    }
+
+    async fn record_insight(&self, insight: &Insight, event_id: Option<&str>) -> Result<String> {
+        let _ = event_id; // no LEARNED_FROM relationship in the flat SQLite cache
+        let insight_id = insight
+            .id
+            .clone()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO insights (id, description, pattern_type, tags, created_at, usage_count, effectiveness_score)
+             VALUES (?1, ?2, ?3, ?4, datetime('now'), 0, ?5)
+             ON CONFLICT(id) DO UPDATE SET
+                description = excluded.description,
+                pattern_type = excluded.pattern_type,
+                tags = excluded.tags,
+                effectiveness_score = excluded.effectiveness_score",
+            params![
+                insight_id,
+                insight.description,
+                insight.pattern_type,
+                insight.tags.as_ref().map(|t| serde_json::to_string(t).unwrap_or_default()),
+                insight.effectiveness_score.unwrap_or(0.0),
+            ],
+        )?;
+        Ok(insight_id)
+    }
+
+    async fn record_insights_batch(
+        &self,
+        insights: &[(Insight, Option<String>)],
+    ) -> Result<Vec<String>> {
+        if insights.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let mut ids = Vec::with_capacity(insights.len());
+        for (insight, _event_id) in insights {
+            // no LEARNED_FROM relationship in the flat SQLite cache
+            let insight_id = insight
+                .id
+                .clone()
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+            tx.execute(
+                "INSERT INTO insights (id, description, pattern_type, tags, created_at, usage_count, effectiveness_score)
+                 VALUES (?1, ?2, ?3, ?4, datetime('now'), 0, ?5)
+                 ON CONFLICT(id) DO UPDATE SET
+                    description = excluded.description,
+                    pattern_type = excluded.pattern_type,
+                    tags = excluded.tags,
+                    effectiveness_score = excluded.effectiveness_score",
+                params![
+                    insight_id,
+                    insight.description,
+                    insight.pattern_type,
+                    insight.tags.as_ref().map(|t| serde_json::to_string(t).unwrap_or_default()),
+                    insight.effectiveness_score.unwrap_or(0.0),
+                ],
+            )?;
+            ids.push(insight_id);
+        }
+        tx.commit()?;
+
+        Ok(ids)
+    }
+
+    async fn get_insights_by_tags(&self, tags: &[String], limit: i64) -> Result<Vec<Insight>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT * FROM insights ORDER BY usage_count DESC, created_at DESC LIMIT ?1",
+        )?;
+        let insights = stmt
+            .query_map(params![limit], Self::row_to_insight)?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+            .into_iter()
+            .filter(|i: &Insight| {
+                i.tags
+                    .as_ref()
+                    .is_some_and(|insight_tags| insight_tags.iter().any(|t| tags.contains(t)))
+            })
+            .collect();
+        Ok(insights)
+    }
+
+    async fn get_insights_by_type(&self, pattern_type: &str, limit: i64) -> Result<Vec<Insight>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT * FROM insights WHERE pattern_type = ?1
+             ORDER BY COALESCE(effectiveness_score, 0) DESC, usage_count DESC, created_at DESC
+             LIMIT ?2",
+        )?;
+        let insights = stmt
+            .query_map(params![pattern_type, limit], Self::row_to_insight)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(insights)
+    }
+
+    async fn increment_insight_usage(&self, insight_id: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE insights SET usage_count = usage_count + 1 WHERE id = ?1",
+            params![insight_id],
+        )?;
+        Ok(())
+    }
+
+    async fn record_insight_feedback(
+        &self,
+        insight_id: &str,
+        positive: bool,
+        half_life_days: Option<f64>,
+    ) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let (pos, total): (i64, i64) = conn.query_row(
+            "SELECT COALESCE(feedback_pos, 0), COALESCE(feedback_total, 0) FROM insights WHERE id = ?1",
+            params![insight_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        let pos = pos + if positive { 1 } else { 0 };
+        let total = total + 1;
+        let score = decayed_effectiveness_score(
+            pos,
+            total,
+            0.0,
+            half_life_days.unwrap_or(DEFAULT_HALF_LIFE_DAYS),
+        );
+
+        conn.execute(
+            "UPDATE insights
+             SET feedback_pos = ?1, feedback_total = ?2, feedback_last_at = datetime('now'), effectiveness_score = ?3
+             WHERE id = ?4",
+            params![pos, total, score, insight_id],
+        )?;
+        Ok(())
+    }
+
+    async fn search_insights(&self, params: InsightSearchParams) -> Result<Vec<Insight>> {
+        let candidates = {
+            let conn = self.conn.lock().unwrap();
+            let mut stmt =
+                conn.prepare("SELECT * FROM insights ORDER BY usage_count DESC LIMIT ?1")?;
+            stmt.query_map(params![SEARCH_CANDIDATE_POOL], Self::row_to_insight)?
+                .collect::<rusqlite::Result<Vec<_>>>()?
+        };
+        Ok(rank_insights(candidates, &params))
+    }
+
+    async fn create_rule(&self, rule: &Rule, project_path: Option<&str>) -> Result<String> {
+        let rule_id = rule
+            .id
+            .clone()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO rules (id, name, description, trigger, action, scope, enforcement, enabled, created_at, triggered_count, source_instruction_count, project_path)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, datetime('now'), 0, ?9, ?10)
+             ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                description = excluded.description,
+                trigger = excluded.trigger,
+                action = excluded.action,
+                scope = excluded.scope,
+                enforcement = excluded.enforcement,
+                enabled = excluded.enabled,
+                source_instruction_count = excluded.source_instruction_count,
+                project_path = excluded.project_path",
+            params![
+                rule_id,
+                rule.name,
+                rule.description,
+                serde_json::to_string(&rule.trigger).unwrap_or_default(),
+                serde_json::to_string(&rule.action).unwrap_or_default(),
+                rule.scope,
+                rule.enforcement,
+                rule.enabled.unwrap_or(true),
+                rule.source_instruction_count.unwrap_or(0),
+                project_path,
+            ],
+        )?;
+        Ok(rule_id)
+    }
+
+    async fn get_rules_by_scope(&self, scope: &str, project_path: Option<&str>) -> Result<Vec<Rule>> {
+        let conn = self.conn.lock().unwrap();
+
+        if scope == "project" && project_path.is_some() {
+            let mut stmt = conn.prepare(
+                "SELECT * FROM rules WHERE scope = 'project' AND project_path = ?1 AND enabled = 1 ORDER BY created_at DESC",
+            )?;
+            let rules = stmt
+                .query_map(params![project_path.unwrap()], Self::row_to_rule)?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(rules)
+        } else {
+            let mut stmt = conn.prepare(
+                "SELECT * FROM rules WHERE scope = ?1 AND enabled = 1 ORDER BY created_at DESC",
+            )?;
+            let rules = stmt
+                .query_map(params![scope], Self::row_to_rule)?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(rules)
+        }
+    }
+
+    async fn get_enabled_rules(&self, project_path: &str) -> Result<Vec<Rule>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT * FROM rules WHERE enabled = 1 AND (scope = 'global' OR (scope = 'project' AND project_path = ?1)) ORDER BY scope, created_at DESC",
+        )?;
+        let rules = stmt
+            .query_map(params![project_path], Self::row_to_rule)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(rules)
+    }
+
+    async fn toggle_rule(&self, rule_id: &str, enabled: bool) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("UPDATE rules SET enabled = ?1 WHERE id = ?2", params![enabled, rule_id])?;
+        Ok(())
+    }
+
+    async fn increment_rule_triggered(&self, rule_id: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE rules SET triggered_count = triggered_count + 1 WHERE id = ?1",
+            params![rule_id],
+        )?;
+        Ok(())
+    }
+
+    async fn record_rule_feedback(
+        &self,
+        rule_id: &str,
+        positive: bool,
+        half_life_days: Option<f64>,
+    ) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let (pos, total): (i64, i64) = conn.query_row(
+            "SELECT COALESCE(feedback_pos, 0), COALESCE(feedback_total, 0) FROM rules WHERE id = ?1",
+            params![rule_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        let pos = pos + if positive { 1 } else { 0 };
+        let total = total + 1;
+        let score = decayed_effectiveness_score(
+            pos,
+            total,
+            0.0,
+            half_life_days.unwrap_or(DEFAULT_HALF_LIFE_DAYS),
+        );
+
+        conn.execute(
+            "UPDATE rules
+             SET feedback_pos = ?1, feedback_total = ?2, feedback_last_at = datetime('now'), effectiveness_score = ?3
+             WHERE id = ?4",
+            params![pos, total, score, rule_id],
+        )?;
+        Ok(())
+    }
+
+    async fn link_rule_to_insight(&self, _rule_id: &str, _insight_id: &str) -> Result<()> {
+        // No DERIVED_FROM relationship in the flat SQLite cache; the graph
+        // remains the source of truth for rule/insight provenance.
+        Ok(())
+    }
+
+    async fn get_lineage(&self, _node_id: &str, _max_depth: i64) -> Result<Vec<LineageEdge>> {
+        // No relationship edges in the flat SQLite cache; the graph remains
+        // the source of truth for provenance traversal.
+        Ok(Vec::new())
+    }
+
+    async fn get_project_stats(&self, project_path: &str) -> Result<ProjectStats> {
+        let conn = self.conn.lock().unwrap();
+
+        let total: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM features WHERE project_path = ?1",
+            params![project_path],
+            |r| r.get(0),
+        )?;
+        let completed: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM features WHERE project_path = ?1 AND status = 'complete'",
+            params![project_path],
+            |r| r.get(0),
+        )?;
+        let in_progress: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM features WHERE project_path = ?1 AND status = 'in_progress'",
+            params![project_path],
+            |r| r.get(0),
+        )?;
+        let active_sessions: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM sessions WHERE project_path = ?1 AND status = 'active'",
+            params![project_path],
+            |r| r.get(0),
+        )?;
+
+        Ok(ProjectStats {
+            total: total as i32,
+            completed: completed as i32,
+            in_progress: in_progress as i32,
+            percentage: if total > 0 {
+                (completed as f64 / total as f64 * 100.0) as i32
+            } else {
+                0
+            },
+            active_sessions: active_sessions as i32,
+        })
+    }
+}
+
+// =============================================================================
+// LAYERED STORE (read-through cache with graph failover)
+// =============================================================================
+
+/// A `GraphStore` that serves reads from a local `SqliteStore` cache,
+/// falling back to the `MemgraphStore` graph on a cache miss and populating
+/// the cache with the entities it finds (`get_all_sessions`,
+/// `query_analytics`, `get_lineage` and `get_project_stats` return the
+/// graph's result without caching it). Writes go to both: the cache first (so
+/// the UI sees them immediately), then the graph, which transparently queues
+/// them in its write-ahead log if it's currently unreachable.
+pub struct LayeredStore {
+    graph: Arc<MemgraphStore>,
+    cache: Arc<SqliteStore>,
+}
+
+impl LayeredStore {
+    pub fn new(graph: Arc<MemgraphStore>, cache: Arc<SqliteStore>) -> Self {
+        Self { graph, cache }
+    }
+}
+
+#[async_trait]
+impl GraphStore for LayeredStore {
+    async fn upsert_project(&self, project: &Project) -> Result<()> {
+        self.cache.upsert_project(project).await?;
+        self.graph.upsert_project(project).await
+    }
+
+    async fn get_projects(&self) -> Result<Vec<Project>> {
+        let cached = self.cache.get_projects().await?;
+        if !cached.is_empty() {
+            return Ok(cached);
+        }
+        let fresh = self.graph.get_projects().await?;
+        for project in &fresh {
+            let _ = self.cache.upsert_project(project).await;
+        }
+        Ok(fresh)
+    }
+
+    async fn get_project_by_path(&self, path: &str) -> Result<Option<Project>> {
+        if let Some(project) = self.cache.get_project_by_path(path).await? {
+            return Ok(Some(project));
+        }
+        let fresh = self.graph.get_project_by_path(path).await?;
+        if let Some(project) = &fresh {
+            let _ = self.cache.upsert_project(project).await;
+        }
+        Ok(fresh)
+    }
+
+    async fn create_feature(&self, feature: &Feature, project_path: &str) -> Result<String> {
+        let mut feature = feature.clone();
+        if feature.id.is_none() {
+            feature.id = Some(uuid::Uuid::new_v4().to_string());
+        }
+        self.cache.create_feature(&feature, project_path).await?;
+        self.graph.create_feature(&feature, project_path).await
+    }
+
+    async fn get_features_for_project(&self, project_path: &str) -> Result<Vec<Feature>> {
+        let cached = self.cache.get_features_for_project(project_path).await?;
+        if !cached.is_empty() {
+            return Ok(cached);
+        }
+        let fresh = self.graph.get_features_for_project(project_path).await?;
+        for feature in &fresh {
+            let _ = self.cache.create_feature(feature, project_path).await;
+        }
+        Ok(fresh)
+    }
+
+    async fn get_active_feature(&self, project_path: &str) -> Result<Option<Feature>> {
+        if let Some(feature) = self.cache.get_active_feature(project_path).await? {
+            return Ok(Some(feature));
+        }
+        let fresh = self.graph.get_active_feature(project_path).await?;
+        if let Some(feature) = &fresh {
+            let _ = self.cache.create_feature(feature, project_path).await;
+        }
+        Ok(fresh)
+    }
+
+    async fn update_feature_status(&self, feature_id: &str, status: &str) -> Result<()> {
+        self.cache.update_feature_status(feature_id, status).await?;
+        self.graph.update_feature_status(feature_id, status).await
+    }
+
+    async fn activate_feature(&self, project_path: &str, feature_id: &str) -> Result<()> {
+        self.cache.activate_feature(project_path, feature_id).await?;
+        self.graph.activate_feature(project_path, feature_id).await
+    }
+
+    async fn complete_feature(&self, feature_id: &str) -> Result<()> {
+        self.cache.complete_feature(feature_id).await?;
+        self.graph.complete_feature(feature_id).await
+    }
+
+    async fn increment_work_count(&self, feature_id: &str) -> Result<i64> {
+        let _ = self.cache.increment_work_count(feature_id).await;
+        self.graph.increment_work_count(feature_id).await
+    }
+
+    async fn record_event(&self, event: &Event, session_id: &str) -> Result<String> {
+        let mut event = event.clone();
+        if event.id.is_none() {
+            event.id = Some(uuid::Uuid::new_v4().to_string());
+        }
+        self.cache.record_event(&event, session_id).await?;
+        self.graph.record_event(&event, session_id).await
+    }
+
+    async fn record_events_batch(&self, events: &[(Event, String)]) -> Result<Vec<String>> {
+        let mut events = events.to_vec();
+        for (event, _) in &mut events {
+            if event.id.is_none() {
+                event.id = Some(uuid::Uuid::new_v4().to_string());
+            }
+        }
+        self.cache.record_events_batch(&events).await?;
+        self.graph.record_events_batch(&events).await
+    }
+
+    async fn link_event_to_feature(&self, event_id: &str, feature_id: &str) -> Result<()> {
+        self.cache.link_event_to_feature(event_id, feature_id).await?;
+        self.graph.link_event_to_feature(event_id, feature_id).await
+    }
+
+    async fn link_events_to_features_batch(&self, links: &[(String, String)]) -> Result<()> {
+        self.cache.link_events_to_features_batch(links).await?;
+        self.graph.link_events_to_features_batch(links).await
+    }
+
+    async fn query_events(&self, filter: EventFilter) -> Result<EventPage> {
+        let cached = self.cache.query_events(filter.clone()).await?;
+        if !cached.events.is_empty() {
+            return Ok(cached);
+        }
+        let fresh = self.graph.query_events(filter).await?;
+        for event in &fresh.events {
+            let session_id = event.session_id.clone().unwrap_or_default();
+            let _ = self.cache.record_event(event, &session_id).await;
+        }
+        Ok(fresh)
+    }
+
+    async fn query_analytics(&self, filter: AnalyticsFilter) -> Result<Vec<AnalyticsBucket>> {
+        let cached = self.cache.query_analytics(filter.clone()).await?;
+        if !cached.is_empty() {
+            return Ok(cached);
+        }
+        self.graph.query_analytics(filter).await
+    }
+
+    async fn start_session(&self, session_id: &str, agent: &str, project_path: &str) -> Result<()> {
+        self.cache.start_session(session_id, agent, project_path).await?;
+        self.graph.start_session(session_id, agent, project_path).await
+    }
+
+    async fn end_session(&self, session_id: &str) -> Result<()> {
+        self.cache.end_session(session_id).await?;
+        self.graph.end_session(session_id).await
+    }
+
+    async fn update_session_activity(&self, session_id: &str) -> Result<()> {
+        self.cache.update_session_activity(session_id).await?;
+        self.graph.update_session_activity(session_id).await
+    }
+
+    async fn get_active_sessions(&self, project_path: &str) -> Result<Vec<Session>> {
+        let cached = self.cache.get_active_sessions(project_path).await?;
+        if !cached.is_empty() {
+            return Ok(cached);
+        }
+        let fresh = self.graph.get_active_sessions(project_path).await?;
+        for session in &fresh {
+            let _ = self.cache.start_session(&session.id, &session.agent, project_path).await;
+        }
+        Ok(fresh)
+    }
+
+    async fn get_all_sessions(&self, limit: i64) -> Result<Vec<Session>> {
+        let cached = self.cache.get_all_sessions(limit).await?;
+        if !cached.is_empty() {
+            return Ok(cached);
+        }
+        Ok(self.graph.get_all_sessions(limit).await?)
+    }
+
+    async fn record_insight(&self, insight: &Insight, event_id: Option<&str>) -> Result<String> {
+        let mut insight = insight.clone();
+        if insight.id.is_none() {
+            insight.id = Some(uuid::Uuid::new_v4().to_string());
+        }
+        self.cache.record_insight(&insight, event_id).await?;
+        self.graph.record_insight(&insight, event_id).await
+    }
+
+    async fn record_insights_batch(
+        &self,
+        insights: &[(Insight, Option<String>)],
+    ) -> Result<Vec<String>> {
+        let mut insights = insights.to_vec();
+        for (insight, _) in &mut insights {
+            if insight.id.is_none() {
+                insight.id = Some(uuid::Uuid::new_v4().to_string());
+            }
+        }
+        self.cache.record_insights_batch(&insights).await?;
+        self.graph.record_insights_batch(&insights).await
+    }
+
+    async fn get_insights_by_tags(&self, tags: &[String], limit: i64) -> Result<Vec<Insight>> {
+        let cached = self.cache.get_insights_by_tags(tags, limit).await?;
+        if !cached.is_empty() {
+            return Ok(cached);
+        }
+        let fresh = self.graph.get_insights_by_tags(tags, limit).await?;
+        for insight in &fresh {
+            let _ = self.cache.record_insight(insight, None).await;
+        }
+        Ok(fresh)
+    }
+
+    async fn get_insights_by_type(&self, pattern_type: &str, limit: i64) -> Result<Vec<Insight>> {
+        let cached = self.cache.get_insights_by_type(pattern_type, limit).await?;
+        if !cached.is_empty() {
+            return Ok(cached);
+        }
+        let fresh = self.graph.get_insights_by_type(pattern_type, limit).await?;
+        for insight in &fresh {
+            let _ = self.cache.record_insight(insight, None).await;
+        }
+        Ok(fresh)
+    }
+
+    async fn increment_insight_usage(&self, insight_id: &str) -> Result<()> {
+        self.cache.increment_insight_usage(insight_id).await?;
+        self.graph.increment_insight_usage(insight_id).await
+    }
+
+    async fn record_insight_feedback(
+        &self,
+        insight_id: &str,
+        positive: bool,
+        half_life_days: Option<f64>,
+    ) -> Result<()> {
+        self.cache
+            .record_insight_feedback(insight_id, positive, half_life_days)
+            .await?;
+        self.graph
+            .record_insight_feedback(insight_id, positive, half_life_days)
+            .await
+    }
+
+    async fn search_insights(&self, params: InsightSearchParams) -> Result<Vec<Insight>> {
+        let cached = self.cache.search_insights(params.clone()).await?;
+        if !cached.is_empty() {
+            return Ok(cached);
+        }
+        let fresh = self.graph.search_insights(params).await?;
+        for insight in &fresh {
+            let _ = self.cache.record_insight(insight, None).await;
+        }
+        Ok(fresh)
+    }
+
+    async fn create_rule(&self, rule: &Rule, project_path: Option<&str>) -> Result<String> {
+        let mut rule = rule.clone();
+        if rule.id.is_none() {
+            rule.id = Some(uuid::Uuid::new_v4().to_string());
+        }
+        self.cache.create_rule(&rule, project_path).await?;
+        self.graph.create_rule(&rule, project_path).await
+    }
+
+    async fn get_rules_by_scope(&self, scope: &str, project_path: Option<&str>) -> Result<Vec<Rule>> {
+        let cached = self.cache.get_rules_by_scope(scope, project_path).await?;
+        if !cached.is_empty() {
+            return Ok(cached);
+        }
+        let fresh = self.graph.get_rules_by_scope(scope, project_path).await?;
+        for rule in &fresh {
+            let _ = self.cache.create_rule(rule, project_path).await;
+        }
+        Ok(fresh)
+    }
+
+    async fn get_enabled_rules(&self, project_path: &str) -> Result<Vec<Rule>> {
+        let cached = self.cache.get_enabled_rules(project_path).await?;
+        if !cached.is_empty() {
+            return Ok(cached);
+        }
+        let fresh = self.graph.get_enabled_rules(project_path).await?;
+        for rule in &fresh {
+            let _ = self.cache.create_rule(rule, Some(project_path)).await;
+        }
+        Ok(fresh)
+    }
+
+    async fn toggle_rule(&self, rule_id: &str, enabled: bool) -> Result<()> {
+        self.cache.toggle_rule(rule_id, enabled).await?;
+        self.graph.toggle_rule(rule_id, enabled).await
+    }
+
+    async fn increment_rule_triggered(&self, rule_id: &str) -> Result<()> {
+        self.cache.increment_rule_triggered(rule_id).await?;
+        self.graph.increment_rule_triggered(rule_id).await
+    }
+
+    async fn record_rule_feedback(
+        &self,
+        rule_id: &str,
+        positive: bool,
+        half_life_days: Option<f64>,
+    ) -> Result<()> {
+        self.cache
+            .record_rule_feedback(rule_id, positive, half_life_days)
+            .await?;
+        self.graph
+            .record_rule_feedback(rule_id, positive, half_life_days)
+            .await
+    }
+
+    async fn link_rule_to_insight(&self, rule_id: &str, insight_id: &str) -> Result<()> {
+        self.cache.link_rule_to_insight(rule_id, insight_id).await?;
+        self.graph.link_rule_to_insight(rule_id, insight_id).await
+    }
+
+    async fn get_lineage(&self, node_id: &str, max_depth: i64) -> Result<Vec<LineageEdge>> {
+        // The SQLite cache never has relationship edges, so this always
+        // falls through to the graph; kept as a trait method (rather than
+        // calling `self.graph` directly) for consistency with every other
+        // `GraphStore` read here.
+        let cached = self.cache.get_lineage(node_id, max_depth).await?;
+        if !cached.is_empty() {
+            return Ok(cached);
+        }
+        self.graph.get_lineage(node_id, max_depth).await
+    }
+
+    async fn get_project_stats(&self, project_path: &str) -> Result<ProjectStats> {
+        let cached = self.cache.get_project_stats(project_path).await?;
+        if cached.total > 0 {
+            return Ok(cached);
+        }
+        self.graph.get_project_stats(project_path).await
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = GraphDbConfig::default();
+        assert_eq!(config.uri, "bolt://localhost:7687");
+        assert_eq!(config.user, "ijoka");
+    }
+
+    #[test]
+    fn test_wilson_lower_bound_no_signals_is_neutral() {
+        assert_eq!(wilson_lower_bound(0, 0), 0.0);
+    }
+
+    #[test]
+    fn test_wilson_lower_bound_favors_established_over_lucky() {
+        // A single positive signal scores lower than a well-established,
+        // mostly-positive track record despite its higher raw proportion
+        // (1/1 against 90/100).
+        let lucky = wilson_lower_bound(1, 1);
+        let established = wilson_lower_bound(90, 100);
+        assert!(lucky < established, "{lucky} should be < {established}");
+    }
+
+    #[test]
+    fn test_recency_decay_halves_at_half_life() {
+        let decay = recency_decay(30.0, 30.0);
+        assert!((decay - 0.5).abs() < 1e-9);
+        assert_eq!(recency_decay(0.0, 30.0), 1.0);
+    }
+
+    #[test]
+    fn test_decayed_effectiveness_score_decays_stale_signals() {
+        let fresh = decayed_effectiveness_score(10, 10, 0.0, DEFAULT_HALF_LIFE_DAYS);
+        let stale = decayed_effectiveness_score(10, 10, 90.0, DEFAULT_HALF_LIFE_DAYS);
+        assert!(stale < fresh);
+    }
+
+    #[test]
+    fn test_levenshtein_distance() {
+        assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+        assert_eq!(levenshtein_distance("retrying", "retrying"), 0);
+        assert_eq!(levenshtein_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn test_rank_insights_keeps_fuzzy_match_drops_unrelated() {
+        let no_match = Insight {
+            id: Some("1".into()),
+            description: "retry failed network requests".into(),
+            pattern_type: "solution".into(),
+            tags: None,
+            created_at: None,
+            usage_count: Some(1),
+            effectiveness_score: None,
+        };
+        let typo = Insight {
+            id: Some("2".into()),
+            description: "retrying a failed conection".into(),
+            pattern_type: "solution".into(),
+            tags: None,
+            created_at: None,
+            usage_count: Some(100),
+            effectiveness_score: None,
+        };
+        let unrelated = Insight {
+            id: Some("3".into()),
+            description: "format the codebase consistently".into(),
+            pattern_type: "best_practice".into(),
+            tags: None,
+            created_at: None,
+            usage_count: Some(1000),
+            effectiveness_score: None,
+        };
+
+        let params = InsightSearchParams::new("connection", 10);
+        let ranked = rank_insights(vec![no_match, typo, unrelated], &params);
+
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].id.as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn test_rank_insights_respects_limit() {
+        let insights: Vec<Insight> = (0..5)
+            .map(|i| Insight {
+                id: Some(i.to_string()),
+                description: "handles offline retries gracefully".into(),
+                pattern_type: "solution".into(),
+                tags: None,
+                created_at: None,
+                usage_count: Some(i),
+                effectiveness_score: None,
+            })
+            .collect();
+
+        let params = InsightSearchParams::new("retries", 2);
+        let ranked = rank_insights(insights, &params);
+        assert_eq!(ranked.len(), 2);
+    }
+
+    #[test]
+    fn test_snapshot_relationships_cover_every_node_label() {
+        let mut referenced = std::collections::HashSet::new();
+        for rel in SNAPSHOT_RELATIONSHIPS {
+            assert!(SNAPSHOT_NODE_LABELS.contains(&rel.from_label));
+            assert!(SNAPSHOT_NODE_LABELS.contains(&rel.to_label));
+            referenced.insert(rel.from_label);
+            referenced.insert(rel.to_label);
+        }
+
+        for label in SNAPSHOT_NODE_LABELS {
+            assert!(
+                referenced.contains(label),
+                "{label} has no relationship in SNAPSHOT_RELATIONSHIPS"
+            );
+        }
+    }
+
+    #[test]
+    fn test_snapshot_manifest_roundtrips_through_json() {
+        let mut counts = std::collections::BTreeMap::new();
+        counts.insert("Project".to_string(), 3);
+        counts.insert("BELONGS_TO".to_string(), 5);
+
+        let manifest = SnapshotManifest {
+            schema_version: SNAPSHOT_SCHEMA_VERSION,
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            counts,
+        };
+
+        let json = serde_json::to_string(&manifest).unwrap();
+        let parsed: SnapshotManifest = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.schema_version, SNAPSHOT_SCHEMA_VERSION);
+        assert_eq!(parsed.counts.get("Project"), Some(&3));
+    }
+
+    #[test]
+    fn test_analytics_filter_builder_composes() {
+        let filter = AnalyticsFilter::new(AnalyticsGroupBy::Agent)
+            .project_path("/repo")
+            .tool_name("Bash")
+            .success(false)
+            .after("2026-01-01")
+            .feature_status("in_progress");
+
+        assert_eq!(filter.group_by.as_str(), "agent");
+        assert_eq!(filter.project_path.as_deref(), Some("/repo"));
+        assert_eq!(filter.tool_name.as_deref(), Some("Bash"));
+        assert_eq!(filter.success, Some(false));
+        assert_eq!(filter.after.as_deref(), Some("2026-01-01"));
+        assert_eq!(filter.feature_status.as_deref(), Some("in_progress"));
+        assert!(filter.source_agent.is_none());
+    }
+
+    #[test]
+    fn test_lineage_relationships_walk_toward_project() {
+        let rels: Vec<&str> = LINEAGE_RELATIONSHIPS.split('|').collect();
+        for expected in [
+            "DERIVED_FROM",
+            "LEARNED_FROM",
+            "TRIGGERED_BY",
+            "LINKED_TO",
+            "BELONGS_TO",
+            "IN_PROJECT",
+            "APPLIES_TO",
+        ] {
+            assert!(
+                rels.contains(&expected),
+                "LINEAGE_RELATIONSHIPS missing {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_lineage_edge_roundtrips_through_json() {
+        let edge = LineageEdge {
+            relationship: "DERIVED_FROM".to_string(),
+            from_label: "Rule".to_string(),
+            from_id: "rule-1".to_string(),
+            to_label: "Insight".to_string(),
+            to_id: "insight-1".to_string(),
+            timestamp: Some("2026-01-01T00:00:00Z".to_string()),
+        };
+        let json = serde_json::to_string(&edge).unwrap();
+        let back: LineageEdge = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.relationship, "DERIVED_FROM");
+        assert_eq!(back.from_id, "rule-1");
+        assert_eq!(back.to_id, "insight-1");
     }
 }
@@ -0,0 +1,88 @@
+//! A Hybrid Logical Clock, used by [`crate::storage`] backends to merge
+//! feature edits made on different machines without relying on wall-clock
+//! agreement between them.
+//!
+//! Each node keeps one `Hlc`. A purely local update advances it with
+//! [`Hlc::tick_local`]; ingesting a timestamp observed from another node
+//! advances it with [`Hlc::merge_remote`]. Two clocks are compared with
+//! [`Hlc::as_tuple`], which orders lexicographically by `(l, c, node_id)` —
+//! the same ordering `Storage::sync_features` uses to decide whether an
+//! incoming feature write should replace what's already stored.
+
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Hlc {
+    pub l: i64,
+    pub c: i64,
+    pub node_id: String,
+}
+
+impl Hlc {
+    pub fn zero(node_id: impl Into<String>) -> Self {
+        Self {
+            l: 0,
+            c: 0,
+            node_id: node_id.into(),
+        }
+    }
+
+    /// Advance this clock for an update made on this node, observing the
+    /// current physical time in milliseconds.
+    pub fn tick_local(&self, physical_now_ms: i64) -> Self {
+        let l = self.l.max(physical_now_ms);
+        let c = if l == self.l { self.c + 1 } else { 0 };
+        Self {
+            l,
+            c,
+            node_id: self.node_id.clone(),
+        }
+    }
+
+    /// Advance this clock after observing a remote timestamp `(remote_l,
+    /// remote_c)`, e.g. while ingesting a feature synced from another
+    /// machine. Whichever of `self.l`, `remote_l`, and the current physical
+    /// time is largest determines which counter gets incremented.
+    pub fn merge_remote(&self, remote_l: i64, remote_c: i64, physical_now_ms: i64) -> Self {
+        let l = self.l.max(remote_l).max(physical_now_ms);
+
+        let c = if l == self.l && l == remote_l {
+            self.c.max(remote_c) + 1
+        } else if l == self.l {
+            self.c + 1
+        } else if l == remote_l {
+            remote_c + 1
+        } else {
+            0
+        };
+
+        Self {
+            l,
+            c,
+            node_id: self.node_id.clone(),
+        }
+    }
+
+    /// Ordering key for conflict resolution: lexicographic `(l, c, node_id)`.
+    pub fn as_tuple(&self) -> (i64, i64, &str) {
+        (self.l, self.c, self.node_id.as_str())
+    }
+}
+
+/// Current wall-clock time in milliseconds since the Unix epoch, clamped to
+/// `0` in the (essentially impossible) case the system clock predates it.
+pub fn physical_now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// A fresh, process-local node identifier, for machines opening a database
+/// for the first time. Stable for the lifetime of the process; callers that
+/// need it to survive restarts (e.g. `SqliteBackend`) persist it themselves.
+pub fn generate_node_id() -> String {
+    format!("node-{:x}-{:x}", physical_now_ms(), std::process::id())
+}
@@ -0,0 +1,287 @@
+//! Durable, resumable background jobs (project scans, feature syncs) that
+//! survive an app restart mid-run.
+//!
+//! Each job's resumable state is a `cursor` (how far through the work it
+//! got) plus an opaque payload, serialized with MessagePack rather than
+//! JSON so a progress update can be persisted cheaply and often without the
+//! app needing to re-derive anything from scratch. On startup,
+//! `JobManager::requeue_stale_running` puts any job still marked `running`
+//! (left over from a process that didn't shut down cleanly) back to
+//! `pending` so its driver can pick it up again from the stored cursor
+//! instead of restarting from zero.
+
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum JobKind {
+    ProjectScan,
+    FeatureSync,
+}
+
+impl JobKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            JobKind::ProjectScan => "project_scan",
+            JobKind::FeatureSync => "feature_sync",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "project_scan" => Some(JobKind::ProjectScan),
+            "feature_sync" => Some(JobKind::FeatureSync),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum JobStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            JobStatus::Pending => "pending",
+            JobStatus::Running => "running",
+            JobStatus::Completed => "completed",
+            JobStatus::Failed => "failed",
+            JobStatus::Cancelled => "cancelled",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(JobStatus::Pending),
+            "running" => Some(JobStatus::Running),
+            "completed" => Some(JobStatus::Completed),
+            "failed" => Some(JobStatus::Failed),
+            "cancelled" => Some(JobStatus::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Job {
+    pub id: i64,
+    pub kind: JobKind,
+    pub project_dir: String,
+    pub status: JobStatus,
+    /// How far through the work this job has progressed — e.g. the number
+    /// of scan roots already walked. Opaque to everything except the kind's
+    /// own resume logic.
+    pub cursor: i64,
+    pub error: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+pub struct JobManager {
+    conn: Mutex<Connection>,
+}
+
+pub struct JobManagerState(pub Arc<JobManager>);
+
+impl JobManager {
+    pub fn new(path: &Path) -> Result<Self, rusqlite::Error> {
+        let conn = Connection::open(path)?;
+
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS jobs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                kind TEXT NOT NULL,
+                project_dir TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'pending',
+                cursor INTEGER NOT NULL DEFAULT 0,
+                payload BLOB,
+                error TEXT,
+                created_at TEXT DEFAULT (datetime('now')),
+                updated_at TEXT DEFAULT (datetime('now'))
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_jobs_status ON jobs(status);
+            "#,
+        )?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Persist a new job in `pending` state and return its id.
+    pub fn enqueue<P: Serialize>(
+        &self,
+        kind: JobKind,
+        project_dir: &str,
+        payload: &P,
+    ) -> Result<i64, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let payload_bytes = encode_payload(payload)?;
+
+        conn.execute(
+            "INSERT INTO jobs (kind, project_dir, status, cursor, payload) VALUES (?1, ?2, 'pending', 0, ?3)",
+            params![kind.as_str(), project_dir, payload_bytes],
+        )?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn mark_running(&self, job_id: i64) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE jobs SET status = 'running', updated_at = datetime('now') WHERE id = ?1",
+            params![job_id],
+        )?;
+        Ok(())
+    }
+
+    /// Advance `job_id`'s cursor and overwrite its resumable payload.
+    pub fn update_progress<P: Serialize>(
+        &self,
+        job_id: i64,
+        cursor: i64,
+        payload: &P,
+    ) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let payload_bytes = encode_payload(payload)?;
+
+        conn.execute(
+            "UPDATE jobs SET cursor = ?1, payload = ?2, updated_at = datetime('now') WHERE id = ?3",
+            params![cursor, payload_bytes, job_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn mark_completed(&self, job_id: i64) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE jobs SET status = 'completed', updated_at = datetime('now') WHERE id = ?1",
+            params![job_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn mark_failed(&self, job_id: i64, error: &str) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE jobs SET status = 'failed', error = ?1, updated_at = datetime('now') WHERE id = ?2",
+            params![error, job_id],
+        )?;
+        Ok(())
+    }
+
+    /// Cancel a job that hasn't finished yet. No-op if it's already in a
+    /// terminal state.
+    pub fn cancel_job(&self, job_id: i64) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE jobs SET status = 'cancelled', updated_at = datetime('now')
+             WHERE id = ?1 AND status IN ('pending', 'running')",
+            params![job_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn is_cancelled(&self, job_id: i64) -> Result<bool, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let status: String = conn.query_row(
+            "SELECT status FROM jobs WHERE id = ?1",
+            params![job_id],
+            |r| r.get(0),
+        )?;
+        Ok(status == "cancelled")
+    }
+
+    pub fn get_jobs(&self) -> Result<Vec<Job>, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, kind, project_dir, status, cursor, error, created_at, updated_at
+             FROM jobs ORDER BY created_at DESC",
+        )?;
+
+        let jobs = stmt
+            .query_map([], |row| {
+                let kind_str: String = row.get(1)?;
+                let status_str: String = row.get(3)?;
+                Ok(Job {
+                    id: row.get(0)?,
+                    kind: JobKind::from_str(&kind_str).unwrap_or(JobKind::ProjectScan),
+                    project_dir: row.get(2)?,
+                    status: JobStatus::from_str(&status_str).unwrap_or(JobStatus::Failed),
+                    cursor: row.get(4)?,
+                    error: row.get(5)?,
+                    created_at: row.get(6)?,
+                    updated_at: row.get(7)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(jobs)
+    }
+
+    /// Load a job's stored MessagePack payload back into `P`, to resume
+    /// work after a restart.
+    pub fn load_payload<P: for<'de> Deserialize<'de>>(
+        &self,
+        job_id: i64,
+    ) -> Result<Option<P>, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let bytes: Option<Vec<u8>> = conn
+            .query_row(
+                "SELECT payload FROM jobs WHERE id = ?1",
+                params![job_id],
+                |r| r.get(0),
+            )
+            .ok();
+
+        match bytes {
+            Some(bytes) => Ok(rmp_serde::from_slice(&bytes).ok()),
+            None => Ok(None),
+        }
+    }
+
+    /// Jobs left in `running` state by a previous process that didn't shut
+    /// down cleanly. Puts them back to `pending` so the next driver pass
+    /// resumes each from its stored cursor rather than restarting from
+    /// scratch. Returns the affected jobs.
+    pub fn requeue_stale_running(&self) -> Result<Vec<Job>, rusqlite::Error> {
+        let stale_ids: Vec<i64> = {
+            let conn = self.conn.lock().unwrap();
+            let mut stmt = conn.prepare("SELECT id FROM jobs WHERE status = 'running'")?;
+            stmt.query_map([], |r| r.get(0))?
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        {
+            let conn = self.conn.lock().unwrap();
+            conn.execute(
+                "UPDATE jobs SET status = 'pending', updated_at = datetime('now') WHERE status = 'running'",
+                [],
+            )?;
+        }
+
+        Ok(self
+            .get_jobs()?
+            .into_iter()
+            .filter(|j| stale_ids.contains(&j.id))
+            .collect())
+    }
+}
+
+fn encode_payload<P: Serialize>(payload: &P) -> Result<Vec<u8>, rusqlite::Error> {
+    rmp_serde::to_vec(payload)
+        .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+}
@@ -1,8 +1,20 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod agent_source;
 mod commands;
+mod correlation;
 mod db;
+mod discovery;
+mod embeddings;
+mod feature_manifest;
+mod git_status;
+mod hlc;
+mod jobs;
+mod replay;
+mod search;
 mod server;
+mod storage;
+mod transcript;
 mod watcher;
 
 use std::sync::Arc;
@@ -18,6 +30,27 @@ fn main() {
     // Initialize logging
     tracing_subscriber::fmt::init();
 
+    // `agentkanban replay <workload.json> [db_path]` runs the transcript
+    // replay harness and exits instead of launching the desktop app. See
+    // `replay` for the benchmark/regression-test this drives.
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("replay") {
+        let Some(workload_path) = args.get(2) else {
+            eprintln!("usage: agentkanban replay <workload.json> [db_path]");
+            std::process::exit(2);
+        };
+        let db_path = args
+            .get(3)
+            .map(std::path::PathBuf::from)
+            .unwrap_or_else(|| std::path::PathBuf::from("replay.db"));
+
+        if let Err(e) = replay::run_cli(std::path::Path::new(workload_path), &db_path) {
+            eprintln!("replay failed: {e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     // Event channel for real-time updates
     let (event_tx, _) = broadcast::channel::<db::AgentEvent>(100);
     let event_tx = Arc::new(event_tx);
@@ -45,15 +78,42 @@ fn main() {
             let database = db::Database::new(&db_path)?;
             app.manage(db::DbState(Arc::new(database)));
 
-            // Start file watcher in background thread
-            let watcher_handle = handle.clone();
-            let watcher_tx = Arc::clone(&event_tx);
-            std::thread::spawn(move || {
-                if let Err(e) = watcher::start_watching(watcher_handle, watcher_tx) {
-                    tracing::error!("File watcher error: {}", e);
+            // Durable job queue for long-running scans/syncs. Anything left
+            // `running` from a process that didn't exit cleanly goes back
+            // to `pending` so it resumes from its stored cursor instead of
+            // restarting from scratch.
+            let job_manager = jobs::JobManager::new(&db_path)?;
+            match job_manager.requeue_stale_running() {
+                Ok(resumed) if !resumed.is_empty() => {
+                    tracing::info!("Requeued {} stale job(s) for resume", resumed.len());
                 }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Failed to requeue stale jobs: {}", e),
+            }
+            app.manage(jobs::JobManagerState(Arc::new(job_manager)));
+
+            // Semantic index over parsed transcript content. Embedding runs
+            // on its own background thread (`EmbeddingQueue`), never the
+            // watcher thread, so file-event latency is unaffected.
+            let embedding_index = Arc::new(embeddings::EmbeddingIndex::new(&db_path)?);
+            let embedding_backend: Arc<dyn embeddings::EmbeddingBackend> =
+                Arc::new(embeddings::HashingEmbeddingBackend);
+            let embedding_queue = Arc::new(embeddings::EmbeddingQueue::start(
+                Arc::clone(&embedding_index),
+                Arc::clone(&embedding_backend),
+            ));
+            app.manage(embeddings::EmbeddingState {
+                index: embedding_index,
+                backend: embedding_backend,
             });
 
+            // Start the file watcher and make it available to commands so
+            // `watch_project`/`unwatch_project` can update it at runtime.
+            match watcher::start_watching(handle.clone(), Arc::clone(&event_tx), embedding_queue) {
+                Ok(watcher_state) => app.manage(watcher_state),
+                Err(e) => tracing::error!("File watcher error: {}", e),
+            }
+
             // Start HTTP server for hook events
             let http_handle = handle.clone();
             let http_tx = Arc::clone(&event_tx);
@@ -122,10 +182,16 @@ fn main() {
             commands::get_feature_events,
             commands::get_sessions,
             commands::get_stats,
+            commands::search,
+            commands::search_sessions,
+            commands::get_project_git_status,
             commands::scan_projects,
             commands::watch_project,
+            commands::unwatch_project,
             commands::get_config,
             commands::save_config,
+            commands::get_jobs,
+            commands::cancel_job,
         ])
         .on_window_event(|window, event| {
             // Minimize to tray instead of closing
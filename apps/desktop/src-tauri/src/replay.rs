@@ -0,0 +1,224 @@
+//! Deterministic replay harness for the event pipeline.
+//!
+//! Feeds recorded transcript `.jsonl` fixtures and `feature_list.json`
+//! snapshots through the same parsing (`AgentSource::parse_entry`), tool-call
+//! correlation (`watcher::resolve_tool_call`), and sync
+//! (`watcher::sync_feature_list`) logic the live watcher uses, but driven by
+//! a JSON "workload" file instead of `notify` — so the parsing hot path can
+//! be benchmarked, and a transcript fixture's expected event counts
+//! (including `ToolCallCompleted`) can be checked, without a real
+//! `~/.claude/projects` directory or a running Tauri app. Run with
+//! `agentkanban replay <workload.json> [db_path]`.
+
+use crate::agent_source::{AgentSource, ClaudeCodeSource};
+use crate::correlation::ToolCallTracker;
+use crate::db::{AgentEvent, Database};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+#[derive(Deserialize)]
+struct Workload {
+    fixtures: Vec<Fixture>,
+}
+
+#[derive(Deserialize)]
+struct Fixture {
+    project_dir: String,
+    #[serde(default = "default_session_id")]
+    session_id: String,
+    /// Recorded transcript `.jsonl` fixture to replay, if any.
+    transcript: Option<PathBuf>,
+    /// Recorded `feature_list.json` snapshot to replay, if any.
+    feature_list: Option<PathBuf>,
+    /// Expected event count per `event_type`, checked after the replay.
+    #[serde(default)]
+    expected: HashMap<String, usize>,
+}
+
+fn default_session_id() -> String {
+    "replay".to_string()
+}
+
+/// Aggregate result of [`run_workload`]: throughput and per-type counts
+/// across every fixture, plus any `expected` counts that didn't match.
+pub struct ReplayReport {
+    pub events_replayed: usize,
+    pub elapsed: Duration,
+    pub by_event_type: HashMap<String, usize>,
+    pub mismatches: Vec<String>,
+}
+
+impl ReplayReport {
+    pub fn events_per_sec(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs == 0.0 {
+            0.0
+        } else {
+            self.events_replayed as f64 / secs
+        }
+    }
+}
+
+/// Replay every fixture in `workload_path` into `db`, timing the run.
+pub fn run_workload(
+    workload_path: &Path,
+    db: &Database,
+) -> Result<ReplayReport, Box<dyn std::error::Error>> {
+    let raw = std::fs::read_to_string(workload_path)?;
+    let workload: Workload = serde_json::from_str(&raw)?;
+    let source = ClaudeCodeSource::new().ok_or("Could not find home directory")?;
+
+    let mut by_event_type: HashMap<String, usize> = HashMap::new();
+    let mut mismatches = Vec::new();
+    let mut events_replayed = 0usize;
+
+    let start = Instant::now();
+
+    for fixture in &workload.fixtures {
+        let mut fixture_counts: HashMap<String, usize> = HashMap::new();
+        // Scoped per-fixture: a fixture's transcript is one self-contained
+        // session, and `ToolCallTracker` keys on `(session_id, tool_use_id)`
+        // anyway, so there's no need for it to outlive the fixture.
+        let tool_calls = ToolCallTracker::new();
+
+        if let Some(transcript_path) = &fixture.transcript {
+            let content = std::fs::read_to_string(transcript_path)?;
+            for line in content.lines() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let Some((tool_name, parsed)) = source.parse_entry(line) else {
+                    continue;
+                };
+                let is_result = tool_name == "ToolResult";
+
+                let event = AgentEvent {
+                    id: None,
+                    event_type: "TranscriptUpdated".to_string(),
+                    source_agent: source.source_agent().to_string(),
+                    session_id: fixture.session_id.clone(),
+                    project_dir: fixture.project_dir.clone(),
+                    tool_name: Some(tool_name.clone()),
+                    payload: parsed.payload.clone(),
+                    feature_id: None,
+                    tool_use_id: parsed.tool_use_id.clone(),
+                    created_at: chrono::Utc::now().to_rfc3339(),
+                };
+
+                let event_id = db.insert_event(&event)?;
+                *fixture_counts.entry(event.event_type.clone()).or_insert(0) += 1;
+                events_replayed += 1;
+
+                let Some(tool_use_id) = parsed.tool_use_id else {
+                    continue;
+                };
+
+                if is_result {
+                    if let Some(completed) = crate::watcher::resolve_tool_call(
+                        db,
+                        &tool_calls,
+                        source.source_agent(),
+                        &fixture.session_id,
+                        &fixture.project_dir,
+                        &tool_use_id,
+                        parsed.payload.as_deref(),
+                    ) {
+                        db.insert_event(&completed)?;
+                        *fixture_counts
+                            .entry(completed.event_type.clone())
+                            .or_insert(0) += 1;
+                        events_replayed += 1;
+                    }
+                } else {
+                    tool_calls.record_call(&fixture.session_id, &tool_use_id, event_id, &tool_name);
+                }
+            }
+        }
+
+        if let Some(feature_list_path) = &fixture.feature_list {
+            let content = std::fs::read_to_string(feature_list_path)?;
+            let newly_completed = crate::watcher::sync_feature_list(
+                db,
+                &fixture.project_dir,
+                feature_list_path,
+                &content,
+            )?;
+
+            for feature in &newly_completed {
+                let event = AgentEvent {
+                    id: None,
+                    event_type: "FeatureCompleted".to_string(),
+                    source_agent: feature
+                        .agent
+                        .clone()
+                        .unwrap_or_else(|| "unknown".to_string()),
+                    session_id: fixture.session_id.clone(),
+                    project_dir: fixture.project_dir.clone(),
+                    tool_name: Some(feature.description.clone()),
+                    payload: Some(serde_json::json!({ "category": feature.category }).to_string()),
+                    feature_id: Some(feature.id.clone()),
+                    tool_use_id: None,
+                    created_at: chrono::Utc::now().to_rfc3339(),
+                };
+
+                db.insert_event(&event)?;
+                *fixture_counts.entry(event.event_type.clone()).or_insert(0) += 1;
+                events_replayed += 1;
+            }
+        }
+
+        for (event_type, count) in &fixture_counts {
+            *by_event_type.entry(event_type.clone()).or_insert(0) += count;
+        }
+
+        for (event_type, &expected_count) in &fixture.expected {
+            let actual = fixture_counts.get(event_type).copied().unwrap_or(0);
+            if actual != expected_count {
+                mismatches.push(format!(
+                    "{}: expected {expected_count} {event_type} event(s), got {actual}",
+                    fixture.project_dir,
+                ));
+            }
+        }
+    }
+
+    Ok(ReplayReport {
+        events_replayed,
+        elapsed: start.elapsed(),
+        by_event_type,
+        mismatches,
+    })
+}
+
+/// CLI entry point for `agentkanban replay <workload.json> [db_path]`. Opens
+/// (or creates) the sqlite database at `db_path`, runs the workload, and
+/// prints a throughput/mismatch report. Returns an error if any fixture's
+/// `expected` counts didn't match, so it doubles as a regression-test gate.
+pub fn run_cli(workload_path: &Path, db_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let db = Database::new(db_path)?;
+    let report = run_workload(workload_path, &db)?;
+
+    println!(
+        "Replayed {} event(s) in {:.3}s ({:.0} events/sec)",
+        report.events_replayed,
+        report.elapsed.as_secs_f64(),
+        report.events_per_sec()
+    );
+
+    let mut types: Vec<_> = report.by_event_type.iter().collect();
+    types.sort_by_key(|(name, _)| name.clone());
+    for (event_type, count) in types {
+        println!("  {event_type}: {count}");
+    }
+
+    if report.mismatches.is_empty() {
+        Ok(())
+    } else {
+        for mismatch in &report.mismatches {
+            eprintln!("mismatch: {mismatch}");
+        }
+        Err(format!("{} expectation mismatch(es)", report.mismatches.len()).into())
+    }
+}
@@ -0,0 +1,111 @@
+use crate::db::Database;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    pub kind: String,
+    pub id: String,
+    pub label: String,
+    pub score: i64,
+}
+
+/// Score `candidate` against `query` as an ordered subsequence match.
+///
+/// Every character of `query` must appear in `candidate`, in order. Each
+/// match earns a base point, a bonus when it immediately follows the
+/// previous match, and a bonus when it lands on a word boundary (start of
+/// string, or right after a space/`-`/`_`/`/`). Skipping over unmatched
+/// characters to find the next match costs a small penalty per character
+/// skipped. Returns `None` if `query` is not a subsequence of `candidate`.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let query_chars: Vec<char> = query.to_lowercase().chars().collect();
+    if query_chars.is_empty() {
+        return Some(0);
+    }
+
+    let cand_chars: Vec<char> = candidate.to_lowercase().chars().collect();
+
+    let mut score: i64 = 0;
+    let mut cand_idx = 0usize;
+    let mut last_matched: Option<usize> = None;
+
+    for &qc in &query_chars {
+        let matched_idx = (cand_idx..cand_chars.len()).find(|&i| cand_chars[i] == qc)?;
+
+        // Penalize each unmatched character we had to skip over.
+        score -= (matched_idx - cand_idx) as i64;
+
+        score += 10;
+
+        if last_matched == Some(matched_idx.wrapping_sub(1)) {
+            score += 5;
+        }
+
+        let at_word_boundary = matched_idx == 0
+            || matches!(cand_chars[matched_idx - 1], ' ' | '-' | '_' | '/');
+        if at_word_boundary {
+            score += 8;
+        }
+
+        last_matched = Some(matched_idx);
+        cand_idx = matched_idx + 1;
+    }
+
+    Some(score)
+}
+
+/// Fuzzy-search features, events, and sessions for `query`, returning the
+/// top `limit` hits ranked by descending score.
+pub fn search(db: &Database, query: &str, limit: i64) -> Result<Vec<SearchHit>, rusqlite::Error> {
+    let mut hits = Vec::new();
+
+    for feature in db.get_features(None)? {
+        let haystack = format!("{} {}", feature.id, feature.description);
+        if let Some(score) = fuzzy_score(query, &haystack) {
+            hits.push(SearchHit {
+                kind: "feature".to_string(),
+                id: feature.id,
+                label: feature.description,
+                score,
+            });
+        }
+    }
+
+    for event in db.get_events(500)? {
+        let haystack = format!(
+            "{} {} {}",
+            event.event_type,
+            event.tool_name.clone().unwrap_or_default(),
+            event.session_id
+        );
+        if let Some(score) = fuzzy_score(query, &haystack) {
+            hits.push(SearchHit {
+                kind: "event".to_string(),
+                id: event.id.map(|id| id.to_string()).unwrap_or_default(),
+                label: haystack,
+                score,
+            });
+        }
+    }
+
+    for session in db.get_sessions()? {
+        let haystack = format!(
+            "{} {} {}",
+            session.session_id, session.source_agent, session.project_dir
+        );
+        if let Some(score) = fuzzy_score(query, &haystack) {
+            hits.push(SearchHit {
+                kind: "session".to_string(),
+                id: session.session_id,
+                label: haystack,
+                score,
+            });
+        }
+    }
+
+    hits.sort_by(|a, b| b.score.cmp(&a.score));
+    hits.truncate(limit.max(0) as usize);
+
+    Ok(hits)
+}
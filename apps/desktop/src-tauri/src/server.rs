@@ -1,15 +1,22 @@
 use crate::db::{AgentEvent, DbState, Feature, Session};
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{Path, Query, Request, State},
+    http::{header::AUTHORIZATION, StatusCode},
+    middleware::{self, Next},
+    response::sse::{Event, KeepAlive, Sse},
+    response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
+use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::{Emitter, Manager};
 use tokio::sync::broadcast;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use tower_http::compression::CompressionLayer;
 use tower_http::cors::{Any, CorsLayer};
 
 #[derive(Clone)]
@@ -23,6 +30,22 @@ pub async fn start_server(
     event_tx: Arc<broadcast::Sender<AgentEvent>>,
     port: u16,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    // Gate every route except `/health` behind a bearer token, if one is
+    // configured (or set via `AGENTKANBAN_AUTH_TOKEN`) — otherwise any local
+    // process can hit this `127.0.0.1`, `Any`-origin server and spoof
+    // `FeatureCompleted` notifications or end sessions.
+    let db: tauri::State<DbState> = app.state();
+    let auth_token = Arc::new(
+        db.0.get_config()
+            .ok()
+            .and_then(|c| c.auth_token)
+            .or_else(|| std::env::var("AGENTKANBAN_AUTH_TOKEN").ok())
+            .filter(|token| !token.is_empty()),
+    );
+    if auth_token.is_some() {
+        tracing::info!("HTTP server requires Authorization: Bearer <token>");
+    }
+
     let state = AppState { app, event_tx };
 
     let cors = CorsLayer::new()
@@ -33,12 +56,20 @@ pub async fn start_server(
     let router = Router::new()
         .route("/health", get(health))
         .route("/events", get(get_events).post(receive_event))
+        .route("/events/stream", get(stream_events))
         .route("/events/feature-update", post(receive_feature_update))
         .route("/events/{id}/link", post(link_event))
         .route("/sessions/start", post(session_start))
         .route("/sessions/end", post(session_end))
         .layer(cors)
-        .with_state(state);
+        .with_state(state)
+        // Gzip/brotli-encode responses for clients that advertise support via
+        // `Accept-Encoding` — event/feature lists can get large once a
+        // dashboard pulls several hundred rows at once. Tiny responses like
+        // `/health` simply don't compress down smaller, so this is a no-op
+        // for them in practice.
+        .layer(CompressionLayer::new())
+        .layer(middleware::from_fn_with_state(auth_token, require_bearer_token));
 
     let listener = tokio::net::TcpListener::bind(format!("127.0.0.1:{}", port)).await?;
 
@@ -49,6 +80,35 @@ pub async fn start_server(
     Ok(())
 }
 
+/// Checks `Authorization: Bearer <token>` against the configured token,
+/// when one is set. `/health` is always reachable unauthenticated, and when
+/// no token is configured every route behaves as before.
+async fn require_bearer_token(
+    State(expected_token): State<Arc<Option<String>>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(expected) = expected_token.as_ref() else {
+        return next.run(request).await;
+    };
+
+    if request.uri().path() == "/health" {
+        return next.run(request).await;
+    }
+
+    let provided = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    if provided == Some(expected.as_str()) {
+        next.run(request).await
+    } else {
+        (StatusCode::UNAUTHORIZED, "unauthorized").into_response()
+    }
+}
+
 async fn health() -> &'static str {
     "OK"
 }
@@ -58,12 +118,28 @@ struct EventsQuery {
     limit: Option<i64>,
     unlinked: Option<bool>,
     project_dir: Option<String>,
+    event_type: Option<String>,
+    /// Cursor for backward paging: only events with `id` less than this are
+    /// returned. Pass the previous page's `next_cursor` to keep paging.
+    /// Not applied when `unlinked` is set.
+    before_id: Option<i64>,
+    source_agent: Option<String>,
+    session_id: Option<String>,
+}
+
+/// Response shape for `GET /events`: a page of events plus the cursor to
+/// pass as `before_id` to fetch the next (older) page. `None` once fewer
+/// than `limit` events came back, i.e. the log's start has been reached.
+#[derive(Serialize)]
+struct EventsPage {
+    events: Vec<AgentEvent>,
+    next_cursor: Option<i64>,
 }
 
 async fn get_events(
     State(state): State<AppState>,
     Query(query): Query<EventsQuery>,
-) -> Json<Vec<AgentEvent>> {
+) -> Json<EventsPage> {
     let db: tauri::State<DbState> = state.app.state();
     let limit = query.limit.unwrap_or(50);
 
@@ -71,10 +147,71 @@ async fn get_events(
         db.0.get_unlinked_events(query.project_dir.as_deref(), limit)
             .unwrap_or_default()
     } else {
-        db.0.get_events(limit).unwrap_or_default()
+        db.0.get_events_page(
+            query.before_id,
+            query.event_type.as_deref(),
+            query.source_agent.as_deref(),
+            query.session_id.as_deref(),
+            limit,
+        )
+        .unwrap_or_default()
     };
 
-    Json(events)
+    let next_cursor = if events.len() >= limit as usize {
+        events.iter().filter_map(|e| e.id).min()
+    } else {
+        None
+    };
+
+    Json(EventsPage { events, next_cursor })
+}
+
+/// Push updates for the live event bus, for consumers without a Tauri
+/// runtime (dashboards, other agents). Each connection gets its own
+/// `broadcast::Receiver`, so a slow consumer only drops its own backlog
+/// (reported as a synthetic `lagged` event) instead of affecting anyone
+/// else subscribed to `state.event_tx`.
+async fn stream_events(
+    State(state): State<AppState>,
+    Query(query): Query<EventsQuery>,
+) -> Sse<impl futures_util::Stream<Item = Result<Event, Infallible>>> {
+    let project_dir = query.project_dir;
+    let event_type = query.event_type;
+    let rx = state.event_tx.subscribe();
+
+    let stream = BroadcastStream::new(rx).filter_map(move |result| {
+        let project_dir = project_dir.clone();
+        let event_type = event_type.clone();
+        async move {
+            let sse_event = match result {
+                Ok(event) => {
+                    if let Some(pd) = &project_dir {
+                        if &event.project_dir != pd {
+                            return None;
+                        }
+                    }
+                    if let Some(et) = &event_type {
+                        if &event.event_type != et {
+                            return None;
+                        }
+                    }
+
+                    let id = event.id.map(|id| id.to_string()).unwrap_or_default();
+                    Event::default()
+                        .json_data(&event)
+                        .unwrap_or_else(|_| Event::default())
+                        .id(id)
+                }
+                Err(BroadcastStreamRecvError::Lagged(skipped)) => Event::default()
+                    .json_data(serde_json::json!({ "type": "lagged", "skipped": skipped }))
+                    .unwrap_or_else(|_| Event::default()),
+            };
+
+            Some(Ok(sse_event))
+        }
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
 }
 
 #[derive(Deserialize)]
@@ -113,6 +250,8 @@ struct IncomingEvent {
     tool_name: Option<String>,
     payload: Option<serde_json::Value>,
     feature_id: Option<String>,
+    #[serde(default)]
+    tool_use_id: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -135,6 +274,7 @@ async fn receive_event(
         tool_name: incoming.tool_name,
         payload: incoming.payload.map(|p| p.to_string()),
         feature_id: incoming.feature_id,
+        tool_use_id: incoming.tool_use_id,
         created_at: chrono::Utc::now().to_rfc3339(),
     };
 
@@ -194,6 +334,7 @@ async fn receive_feature_update(
             tool_name: Some(feature.description.clone()),
             payload: Some(serde_json::json!({ "category": feature.category }).to_string()),
             feature_id: None,
+            tool_use_id: None,
             created_at: chrono::Utc::now().to_rfc3339(),
         };
 
@@ -256,6 +397,11 @@ async fn session_start(
             tracing::info!("Auto-registered new project: {}", project_dir);
             // Sync features from feature_list.json if it exists
             sync_features_from_file(&db, project_dir, &state.app);
+            // Start a debounced watch on feature_list.json so edits made
+            // mid-session (not just the snapshot read above) update the
+            // database and frontend in real time, instead of waiting for
+            // the next `SessionStart`.
+            watch_feature_list(&state.app, project_dir);
         }
         Ok(false) => {
             // Project already registered, still sync features in case file changed
@@ -276,6 +422,7 @@ async fn session_start(
         tool_name: None,
         payload: None,
         feature_id: None,
+        tool_use_id: None,
         created_at: now,
     };
 
@@ -285,26 +432,36 @@ async fn session_start(
     Json(ApiResponse { ok: true, error: None })
 }
 
-/// Sync features from feature_list.json file to database
-fn sync_features_from_file(db: &tauri::State<DbState>, project_dir: &str, app: &tauri::AppHandle) {
-    let feature_file = PathBuf::from(project_dir).join("feature_list.json");
+/// Register a debounced `notify` watch on `project_dir` (idempotent — see
+/// `watcher::ProjectWatcher::watch_project`) so `feature_list.json` edits
+/// made mid-session are picked up the same way a manual `watch_project`
+/// command invocation would.
+fn watch_feature_list(app: &tauri::AppHandle, project_dir: &str) {
+    let watcher: tauri::State<crate::watcher::WatcherState> = app.state();
+    if let Err(e) = watcher.0.watch_project(std::path::Path::new(project_dir)) {
+        tracing::error!("Failed to watch {}: {}", project_dir, e);
+    }
+}
 
-    if !feature_file.exists() {
+/// Sync features from the project's feature manifest
+/// (`feature_list.{json,toml,yaml,yml}`) to the database.
+fn sync_features_from_file(db: &tauri::State<DbState>, project_dir: &str, app: &tauri::AppHandle) {
+    let Some(feature_file) = crate::feature_manifest::find(&PathBuf::from(project_dir)) else {
         return;
-    }
+    };
 
     let content = match std::fs::read_to_string(&feature_file) {
         Ok(c) => c,
         Err(e) => {
-            tracing::error!("Failed to read feature_list.json: {}", e);
+            tracing::error!("Failed to read feature manifest {:?}: {}", feature_file, e);
             return;
         }
     };
 
-    let features: Vec<serde_json::Value> = match serde_json::from_str(&content) {
+    let features = match crate::feature_manifest::parse(&feature_file, &content) {
         Ok(f) => f,
         Err(e) => {
-            tracing::error!("Failed to parse feature_list.json: {}", e);
+            tracing::error!("Failed to parse feature manifest {:?}: {}", feature_file, e);
             return;
         }
     };
@@ -331,6 +488,7 @@ fn sync_features_from_file(db: &tauri::State<DbState>, project_dir: &str, app: &
                 agent: f["agent"].as_str().map(String::from),
                 steps,
                 updated_at: chrono::Utc::now().to_rfc3339(),
+                ..Default::default()
             }
         })
         .collect();
@@ -371,6 +529,7 @@ async fn session_end(
         tool_name: None,
         payload: None,
         feature_id: None,
+        tool_use_id: None,
         created_at: chrono::Utc::now().to_rfc3339(),
     };
 
@@ -0,0 +1,161 @@
+//! Pluggable storage backends for [`crate::db::Database`].
+//!
+//! `Database` itself only owns cross-backend state (the in-memory git status
+//! cache) and composes the raw operations below into the higher-level API
+//! consumed by `commands.rs`/`server.rs`. Everything backend-specific —
+//! connections, schema, query syntax — lives behind the [`Storage`] trait so
+//! a deployment can pick `backend_sqlite` (the default) or `backend_rocksdb`
+//! at compile time without the rest of the app noticing, the same way a
+//! matrix homeserver picks its key-value engine behind one storage trait.
+
+use crate::db::{AgentEvent, Config, Feature, Session, Stats};
+use std::fmt;
+
+/// Error type shared by every [`Storage`] implementation, so backends with
+/// unrelated underlying error types can still be used interchangeably
+/// through one `Result`.
+#[derive(Debug)]
+pub enum StorageError {
+    Sqlite(rusqlite::Error),
+    #[cfg(feature = "backend_rocksdb")]
+    RocksDb(rocksdb::Error),
+    Serialization(serde_json::Error),
+    /// Failed to check out a pooled connection (e.g. the pool is exhausted
+    /// or every connection failed its health check).
+    Pool(String),
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Sqlite(e) => write!(f, "sqlite storage error: {e}"),
+            #[cfg(feature = "backend_rocksdb")]
+            StorageError::RocksDb(e) => write!(f, "rocksdb storage error: {e}"),
+            StorageError::Serialization(e) => write!(f, "storage serialization error: {e}"),
+            StorageError::Pool(e) => write!(f, "connection pool error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StorageError::Sqlite(e) => Some(e),
+            #[cfg(feature = "backend_rocksdb")]
+            StorageError::RocksDb(e) => Some(e),
+            StorageError::Serialization(e) => Some(e),
+            StorageError::Pool(_) => None,
+        }
+    }
+}
+
+impl From<r2d2::Error> for StorageError {
+    fn from(e: r2d2::Error) -> Self {
+        StorageError::Pool(e.to_string())
+    }
+}
+
+impl From<rusqlite::Error> for StorageError {
+    fn from(e: rusqlite::Error) -> Self {
+        StorageError::Sqlite(e)
+    }
+}
+
+#[cfg(feature = "backend_rocksdb")]
+impl From<rocksdb::Error> for StorageError {
+    fn from(e: rocksdb::Error) -> Self {
+        StorageError::RocksDb(e)
+    }
+}
+
+impl From<serde_json::Error> for StorageError {
+    fn from(e: serde_json::Error) -> Self {
+        StorageError::Serialization(e)
+    }
+}
+
+/// Bridges a [`StorageError`] back into `rusqlite::Error` so call sites that
+/// predate the `Storage` split (notably `search.rs`) keep working against a
+/// concrete error type without caring which backend is active.
+impl From<StorageError> for rusqlite::Error {
+    fn from(e: StorageError) -> Self {
+        match e {
+            StorageError::Sqlite(inner) => inner,
+            other => rusqlite::Error::ToSqlConversionFailure(Box::new(other)),
+        }
+    }
+}
+
+/// The raw event/feature/session/config operations a storage engine must
+/// provide. Schema migrations, the git-status cache, and composed helpers
+/// like `add_watched_project` stay on `Database` — this trait only covers
+/// what genuinely differs per backend.
+pub trait Storage: Send + Sync {
+    /// The schema/format version this backend's data is currently stored at.
+    /// For `SqliteBackend` this tracks `PRAGMA user_version` via
+    /// `migrations::apply`; backends without a versioned migration path of
+    /// their own can return a fixed constant.
+    fn schema_version(&self) -> Result<u32, StorageError>;
+
+    fn insert_event(&self, event: &AgentEvent) -> Result<i64, StorageError>;
+    fn get_events(&self, limit: i64) -> Result<Vec<AgentEvent>, StorageError>;
+    fn get_events_by_feature(
+        &self,
+        feature_id: &str,
+        limit: i64,
+    ) -> Result<Vec<AgentEvent>, StorageError>;
+    /// Cursor-paginated, filterable view over the event log: every event
+    /// with `id < before_id` (when given) matching every supplied equality
+    /// filter, newest first. `get_events`/`get_events_by_feature` above stay
+    /// as thin "just the most recent N" conveniences over the same table.
+    fn get_events_page(
+        &self,
+        before_id: Option<i64>,
+        event_type: Option<&str>,
+        source_agent: Option<&str>,
+        session_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<AgentEvent>, StorageError>;
+    /// Events not yet linked to any feature (`feature_id IS NULL`), newest
+    /// first — the hook-review queue `link_event_to_feature` triages.
+    fn get_unlinked_events(
+        &self,
+        project_dir: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<AgentEvent>, StorageError>;
+    /// Link an event to a feature after the fact. Returns `false` if no
+    /// event with that id exists.
+    fn link_event_to_feature(&self, event_id: i64, feature_id: &str) -> Result<bool, StorageError>;
+    /// Merge a tool call's result (`isError`/`resultPreview`/`durationMs`)
+    /// into the originating event's `payload`, once the matching
+    /// `tool_use_id` arrives. A no-op if `event_id` doesn't exist.
+    fn annotate_tool_call(
+        &self,
+        event_id: i64,
+        is_error: bool,
+        result_preview: &str,
+        duration_ms: i64,
+    ) -> Result<(), StorageError>;
+    fn sync_features(
+        &self,
+        project_dir: &str,
+        features: Vec<Feature>,
+    ) -> Result<(), StorageError>;
+    fn get_features(&self, project_dir: Option<&str>) -> Result<Vec<Feature>, StorageError>;
+    fn get_sessions(&self) -> Result<Vec<Session>, StorageError>;
+    fn upsert_session(&self, session: &Session) -> Result<(), StorageError>;
+    fn update_session_status(&self, session_id: &str, status: &str) -> Result<(), StorageError>;
+    /// Returns aggregate counts. `dirty_projects` is always `0` here —
+    /// `Database::get_stats` fills it in from the in-memory git status cache,
+    /// which lives above the storage layer.
+    fn get_stats(&self) -> Result<Stats, StorageError>;
+    fn get_config(&self) -> Result<Config, StorageError>;
+    fn save_config(&self, config: &Config) -> Result<(), StorageError>;
+    fn get_projects(&self) -> Result<Vec<String>, StorageError>;
+}
+
+mod migrations;
+pub mod sqlite_backend;
+
+#[cfg(feature = "backend_rocksdb")]
+pub mod rocksdb_backend;
@@ -0,0 +1,115 @@
+//! Ordered schema migrations for [`super::sqlite_backend::SqliteBackend`],
+//! driven by SQLite's `PRAGMA user_version`.
+//!
+//! Each entry bumps the version by exactly one step inside its own
+//! transaction, so a fresh database and one upgraded from an older version
+//! converge on the same schema deterministically, instead of the previous
+//! try-and-ignore `ALTER TABLE` statements that silently swallowed errors.
+
+use rusqlite::Connection;
+
+pub struct Migration {
+    pub version: u32,
+    pub sql: &'static str,
+}
+
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        sql: r#"
+            CREATE TABLE IF NOT EXISTS events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                event_type TEXT NOT NULL,
+                source_agent TEXT NOT NULL,
+                session_id TEXT NOT NULL,
+                project_dir TEXT NOT NULL,
+                tool_name TEXT,
+                payload TEXT,
+                created_at TEXT DEFAULT (datetime('now'))
+            );
+
+            CREATE TABLE IF NOT EXISTS features (
+                id TEXT PRIMARY KEY,
+                project_dir TEXT NOT NULL,
+                description TEXT NOT NULL,
+                category TEXT DEFAULT 'functional',
+                passes INTEGER DEFAULT 0,
+                in_progress INTEGER DEFAULT 0,
+                agent TEXT,
+                updated_at TEXT DEFAULT (datetime('now'))
+            );
+
+            CREATE TABLE IF NOT EXISTS sessions (
+                session_id TEXT PRIMARY KEY,
+                source_agent TEXT NOT NULL,
+                project_dir TEXT NOT NULL,
+                started_at TEXT DEFAULT (datetime('now')),
+                last_activity TEXT DEFAULT (datetime('now')),
+                status TEXT DEFAULT 'active'
+            );
+
+            CREATE TABLE IF NOT EXISTS config (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_events_session ON events(session_id);
+            CREATE INDEX IF NOT EXISTS idx_events_project ON events(project_dir);
+            CREATE INDEX IF NOT EXISTS idx_events_created ON events(created_at DESC);
+            CREATE INDEX IF NOT EXISTS idx_features_project ON features(project_dir);
+        "#,
+    },
+    Migration {
+        version: 2,
+        sql: r#"
+            ALTER TABLE events ADD COLUMN feature_id TEXT;
+            CREATE INDEX IF NOT EXISTS idx_events_feature_id ON events(feature_id);
+        "#,
+    },
+    Migration {
+        version: 3,
+        sql: "ALTER TABLE features ADD COLUMN steps TEXT;",
+    },
+    Migration {
+        version: 4,
+        sql: r#"
+            ALTER TABLE features ADD COLUMN hlc_l INTEGER NOT NULL DEFAULT 0;
+            ALTER TABLE features ADD COLUMN hlc_c INTEGER NOT NULL DEFAULT 0;
+            ALTER TABLE features ADD COLUMN hlc_node TEXT NOT NULL DEFAULT '';
+        "#,
+    },
+    Migration {
+        version: 5,
+        sql: r#"
+            ALTER TABLE events ADD COLUMN tool_use_id TEXT;
+            CREATE INDEX IF NOT EXISTS idx_events_tool_use_id ON events(tool_use_id);
+        "#,
+    },
+];
+
+/// Schema version this binary knows how to read and write. Bump alongside
+/// adding a new entry to [`MIGRATIONS`].
+pub const CURRENT_SCHEMA_VERSION: u32 = MIGRATIONS[MIGRATIONS.len() - 1].version;
+
+/// Bring `conn` up to [`CURRENT_SCHEMA_VERSION`], applying only the
+/// migrations newer than its current `user_version`. Fails loudly if the
+/// database's `user_version` is ahead of what this binary understands,
+/// rather than silently operating on an unrecognized schema.
+pub fn apply(conn: &mut Connection) -> Result<(), rusqlite::Error> {
+    let current: u32 = conn.query_row("PRAGMA user_version", [], |r| r.get(0))?;
+
+    if current > CURRENT_SCHEMA_VERSION {
+        return Err(rusqlite::Error::ModuleError(format!(
+            "database schema version {current} is newer than this binary supports (max {CURRENT_SCHEMA_VERSION}); upgrade the app before opening this database"
+        )));
+    }
+
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+        let tx = conn.transaction()?;
+        tx.execute_batch(migration.sql)?;
+        tx.pragma_update(None, "user_version", migration.version)?;
+        tx.commit()?;
+    }
+
+    Ok(())
+}
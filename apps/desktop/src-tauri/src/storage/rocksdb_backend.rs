@@ -0,0 +1,421 @@
+//! A RocksDB-backed [`Storage`] implementation for write-heavy hook
+//! ingestion, where SQLite's single-writer lock becomes the bottleneck.
+//!
+//! Each entity gets its own column family, keyed by `(project_dir, id)` so a
+//! single prefix iterator over a project's events/features/sessions never
+//! has to scan rows belonging to another project. Events additionally get a
+//! zero-padded numeric id (from the `meta` column family's counter) so
+//! lexicographic key order matches insertion order within a project.
+
+use super::{Storage, StorageError};
+use crate::db::{AgentEvent, Config, Feature, Session, Stats};
+use crate::hlc::{generate_node_id, physical_now_ms, Hlc};
+use rocksdb::{ColumnFamilyDescriptor, IteratorMode, Options, DB};
+use std::path::Path;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Mutex;
+
+const CF_EVENTS: &str = "events";
+const CF_FEATURES: &str = "features";
+const CF_SESSIONS: &str = "sessions";
+const CF_CONFIG: &str = "config";
+const CF_META: &str = "meta";
+
+const META_NEXT_EVENT_ID: &[u8] = b"next_event_id";
+const META_HLC_NODE_ID: &[u8] = b"hlc_node_id";
+const CONFIG_KEY: &[u8] = b"main";
+
+fn event_key(project_dir: &str, id: i64) -> Vec<u8> {
+    // Zero-padded so byte-lexicographic order matches numeric order.
+    format!("{project_dir}\0{id:020}").into_bytes()
+}
+
+fn feature_key(project_dir: &str, id: &str) -> Vec<u8> {
+    format!("{project_dir}\0{id}").into_bytes()
+}
+
+fn session_key(project_dir: &str, session_id: &str) -> Vec<u8> {
+    format!("{project_dir}\0{session_id}").into_bytes()
+}
+
+pub struct RocksDbBackend {
+    db: DB,
+    next_event_id: AtomicI64,
+    /// This node's Hybrid Logical Clock, advanced on every feature sync. See
+    /// `hlc::Hlc` and `Storage::sync_features`.
+    hlc: Mutex<Hlc>,
+}
+
+impl RocksDbBackend {
+    pub fn open(path: &Path) -> Result<Self, StorageError> {
+        let mut db_opts = Options::default();
+        db_opts.create_if_missing(true);
+        db_opts.create_missing_column_families(true);
+
+        let cf_opts = Options::default();
+        let cfs = [CF_EVENTS, CF_FEATURES, CF_SESSIONS, CF_CONFIG, CF_META]
+            .into_iter()
+            .map(|name| ColumnFamilyDescriptor::new(name, cf_opts.clone()))
+            .collect::<Vec<_>>();
+
+        let db = DB::open_cf_descriptors(&db_opts, path, cfs)?;
+
+        let next_event_id = {
+            let meta = db.cf_handle(CF_META).expect("meta column family exists");
+            match db.get_cf(&meta, META_NEXT_EVENT_ID)? {
+                Some(bytes) => {
+                    let arr: [u8; 8] = bytes.as_slice().try_into().unwrap_or([0; 8]);
+                    i64::from_be_bytes(arr)
+                }
+                None => 0,
+            }
+        };
+
+        let node_id = {
+            let meta = db.cf_handle(CF_META).expect("meta column family exists");
+            match db.get_cf(&meta, META_HLC_NODE_ID)? {
+                Some(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+                None => {
+                    let id = generate_node_id();
+                    db.put_cf(&meta, META_HLC_NODE_ID, id.as_bytes())?;
+                    id
+                }
+            }
+        };
+
+        Ok(Self {
+            db,
+            next_event_id: AtomicI64::new(next_event_id),
+            hlc: Mutex::new(Hlc::zero(node_id)),
+        })
+    }
+
+    fn cf(&self, name: &str) -> &rocksdb::ColumnFamily {
+        self.db.cf_handle(name).expect("column family registered at open")
+    }
+}
+
+impl Storage for RocksDbBackend {
+    fn schema_version(&self) -> Result<u32, StorageError> {
+        // No versioned migration path for this backend yet — every value is
+        // a plain JSON encoding of the current `db` structs, so there's
+        // nothing to converge between versions. Revisit once the on-disk
+        // encoding needs to change under existing deployments.
+        Ok(super::migrations::CURRENT_SCHEMA_VERSION)
+    }
+
+    fn insert_event(&self, event: &AgentEvent) -> Result<i64, StorageError> {
+        let id = self.next_event_id.fetch_add(1, Ordering::SeqCst);
+
+        let mut stamped = event.clone();
+        stamped.id = Some(id);
+        if stamped.created_at.is_empty() {
+            stamped.created_at = "1970-01-01T00:00:00Z".to_string();
+        }
+
+        let key = event_key(&event.project_dir, id);
+        let value = serde_json::to_vec(&stamped)?;
+        self.db.put_cf(&self.cf(CF_EVENTS), key, value)?;
+        self.db.put_cf(
+            &self.cf(CF_META),
+            META_NEXT_EVENT_ID,
+            (id + 1).to_be_bytes(),
+        )?;
+
+        Ok(id)
+    }
+
+    fn get_events(&self, limit: i64) -> Result<Vec<AgentEvent>, StorageError> {
+        // No secondary "by recency across all projects" index exists yet, so
+        // this does a full reverse scan of the column family. Acceptable for
+        // the dashboard's "recent events" view; revisit with a global
+        // sequence index if this becomes a hot path.
+        let mut events: Vec<AgentEvent> = self
+            .db
+            .iterator_cf(&self.cf(CF_EVENTS), IteratorMode::End)
+            .filter_map(|row| row.ok())
+            .filter_map(|(_, value)| serde_json::from_slice::<AgentEvent>(&value).ok())
+            .collect();
+
+        events.sort_by(|a, b| b.id.cmp(&a.id));
+        events.truncate(limit.max(0) as usize);
+        Ok(events)
+    }
+
+    fn get_events_by_feature(
+        &self,
+        feature_id: &str,
+        limit: i64,
+    ) -> Result<Vec<AgentEvent>, StorageError> {
+        let mut events: Vec<AgentEvent> = self
+            .db
+            .iterator_cf(&self.cf(CF_EVENTS), IteratorMode::End)
+            .filter_map(|row| row.ok())
+            .filter_map(|(_, value)| serde_json::from_slice::<AgentEvent>(&value).ok())
+            .filter(|e| e.feature_id.as_deref() == Some(feature_id))
+            .collect();
+
+        events.sort_by(|a, b| b.id.cmp(&a.id));
+        events.truncate(limit.max(0) as usize);
+        Ok(events)
+    }
+
+    fn get_events_page(
+        &self,
+        before_id: Option<i64>,
+        event_type: Option<&str>,
+        source_agent: Option<&str>,
+        session_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<AgentEvent>, StorageError> {
+        let mut events: Vec<AgentEvent> = self
+            .db
+            .iterator_cf(&self.cf(CF_EVENTS), IteratorMode::End)
+            .filter_map(|row| row.ok())
+            .filter_map(|(_, value)| serde_json::from_slice::<AgentEvent>(&value).ok())
+            .filter(|e| before_id.map(|id| e.id.map(|eid| eid < id).unwrap_or(false)).unwrap_or(true))
+            .filter(|e| event_type.map(|t| e.event_type == t).unwrap_or(true))
+            .filter(|e| source_agent.map(|a| e.source_agent == a).unwrap_or(true))
+            .filter(|e| session_id.map(|s| e.session_id == s).unwrap_or(true))
+            .collect();
+
+        events.sort_by(|a, b| b.id.cmp(&a.id));
+        events.truncate(limit.max(0) as usize);
+        Ok(events)
+    }
+
+    fn get_unlinked_events(
+        &self,
+        project_dir: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<AgentEvent>, StorageError> {
+        let mut events: Vec<AgentEvent> = self
+            .db
+            .iterator_cf(&self.cf(CF_EVENTS), IteratorMode::End)
+            .filter_map(|row| row.ok())
+            .filter_map(|(_, value)| serde_json::from_slice::<AgentEvent>(&value).ok())
+            .filter(|e| e.feature_id.is_none())
+            .filter(|e| project_dir.map(|d| e.project_dir == d).unwrap_or(true))
+            .collect();
+
+        events.sort_by(|a, b| b.id.cmp(&a.id));
+        events.truncate(limit.max(0) as usize);
+        Ok(events)
+    }
+
+    fn link_event_to_feature(&self, event_id: i64, feature_id: &str) -> Result<bool, StorageError> {
+        // Events are keyed by `(project_dir, id)` and the project isn't known
+        // here, so find the row the same way `annotate_tool_call` does: by
+        // its id suffix, which is unique across projects since
+        // `next_event_id` is a single global counter.
+        let suffix = event_key("", event_id);
+        let matching = self
+            .db
+            .iterator_cf(&self.cf(CF_EVENTS), IteratorMode::Start)
+            .filter_map(|row| row.ok())
+            .find(|(key, _)| key.ends_with(suffix.as_slice()));
+
+        let Some((key, value)) = matching else {
+            return Ok(false);
+        };
+
+        let mut event: AgentEvent = serde_json::from_slice(&value)?;
+        event.feature_id = Some(feature_id.to_string());
+        self.db.put_cf(&self.cf(CF_EVENTS), key, serde_json::to_vec(&event)?)?;
+        Ok(true)
+    }
+
+    fn annotate_tool_call(
+        &self,
+        event_id: i64,
+        is_error: bool,
+        result_preview: &str,
+        duration_ms: i64,
+    ) -> Result<(), StorageError> {
+        // Events are keyed by `(project_dir, id)` and the project isn't
+        // known here, so find the row the same way `update_session_status`
+        // finds a session: by its id suffix, which is unique across
+        // projects since `next_event_id` is a single global counter.
+        let suffix = event_key("", event_id);
+        let matching = self
+            .db
+            .iterator_cf(&self.cf(CF_EVENTS), IteratorMode::Start)
+            .filter_map(|row| row.ok())
+            .find(|(key, _)| key.ends_with(suffix.as_slice()));
+
+        if let Some((key, value)) = matching {
+            if let Ok(mut event) = serde_json::from_slice::<AgentEvent>(&value) {
+                let mut merged: serde_json::Value = event
+                    .payload
+                    .as_deref()
+                    .and_then(|p| serde_json::from_str(p).ok())
+                    .unwrap_or_else(|| serde_json::json!({}));
+                merged["isError"] = serde_json::json!(is_error);
+                merged["resultPreview"] = serde_json::json!(result_preview);
+                merged["durationMs"] = serde_json::json!(duration_ms);
+                event.payload = Some(merged.to_string());
+
+                let updated = serde_json::to_vec(&event)?;
+                self.db.put_cf(&self.cf(CF_EVENTS), key, updated)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn sync_features(&self, project_dir: &str, features: Vec<Feature>) -> Result<(), StorageError> {
+        for mut feature in features {
+            feature.project_dir = project_dir.to_string();
+
+            // A feature with no `hlc` is a local observation — stamp it
+            // with this node's own advancing clock. One that already
+            // carries an `hlc` is being merged in from another machine's
+            // sync, so fold its timestamp into ours to keep this node's
+            // clock correctly advanced, but resolve the conflict below
+            // against the incoming feature's own tuple, not the post-merge
+            // local clock (which is always ~now and would always "win").
+            let winning_hlc = {
+                let mut clock = self.hlc.lock().unwrap();
+                match &feature.hlc {
+                    Some(remote) => {
+                        *clock = clock.merge_remote(remote.l, remote.c, physical_now_ms());
+                        remote.clone()
+                    }
+                    None => {
+                        *clock = clock.tick_local(physical_now_ms());
+                        clock.clone()
+                    }
+                }
+            };
+
+            let key = feature_key(project_dir, &feature.id);
+            let stored_hlc = self
+                .db
+                .get_cf(&self.cf(CF_FEATURES), &key)?
+                .and_then(|bytes| serde_json::from_slice::<Feature>(&bytes).ok())
+                .and_then(|f| f.hlc);
+
+            if let Some(stored) = &stored_hlc {
+                if stored.as_tuple() >= winning_hlc.as_tuple() {
+                    // Stored feature's clock already dominates (or matches)
+                    // the incoming one — idempotent no-op.
+                    continue;
+                }
+            }
+
+            feature.hlc = Some(winning_hlc);
+            let value = serde_json::to_vec(&feature)?;
+            self.db.put_cf(&self.cf(CF_FEATURES), key, value)?;
+        }
+        Ok(())
+    }
+
+    fn get_features(&self, project_dir: Option<&str>) -> Result<Vec<Feature>, StorageError> {
+        let prefix = project_dir.map(|dir| format!("{dir}\0"));
+
+        let mut features: Vec<Feature> = self
+            .db
+            .iterator_cf(&self.cf(CF_FEATURES), IteratorMode::Start)
+            .filter_map(|row| row.ok())
+            .filter_map(|(key, value)| {
+                if let Some(ref prefix) = prefix {
+                    if !key.starts_with(prefix.as_bytes()) {
+                        return None;
+                    }
+                }
+                serde_json::from_slice::<Feature>(&value).ok()
+            })
+            .collect();
+
+        features.sort_by(|a, b| (a.project_dir.as_str(), a.id.as_str()).cmp(&(b.project_dir.as_str(), b.id.as_str())));
+        Ok(features)
+    }
+
+    fn get_sessions(&self) -> Result<Vec<Session>, StorageError> {
+        let mut sessions: Vec<Session> = self
+            .db
+            .iterator_cf(&self.cf(CF_SESSIONS), IteratorMode::Start)
+            .filter_map(|row| row.ok())
+            .filter_map(|(_, value)| serde_json::from_slice::<Session>(&value).ok())
+            .filter(|s| s.status == "active")
+            .collect();
+
+        sessions.sort_by(|a, b| b.last_activity.cmp(&a.last_activity));
+        Ok(sessions)
+    }
+
+    fn upsert_session(&self, session: &Session) -> Result<(), StorageError> {
+        let key = session_key(&session.project_dir, &session.session_id);
+        let value = serde_json::to_vec(session)?;
+        self.db.put_cf(&self.cf(CF_SESSIONS), key, value)?;
+        Ok(())
+    }
+
+    fn update_session_status(&self, session_id: &str, status: &str) -> Result<(), StorageError> {
+        let key_suffix = format!("\0{session_id}");
+        let matching = self
+            .db
+            .iterator_cf(&self.cf(CF_SESSIONS), IteratorMode::Start)
+            .filter_map(|row| row.ok())
+            .find(|(key, _)| key.ends_with(key_suffix.as_bytes()));
+
+        if let Some((key, value)) = matching {
+            if let Ok(mut session) = serde_json::from_slice::<Session>(&value) {
+                session.status = status.to_string();
+                let updated = serde_json::to_vec(&session)?;
+                self.db.put_cf(&self.cf(CF_SESSIONS), key, updated)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn get_stats(&self) -> Result<Stats, StorageError> {
+        let features = self.get_features(None)?;
+        let total = features.len() as i64;
+        let completed = features.iter().filter(|f| f.passes).count() as i64;
+        let in_progress = features.iter().filter(|f| f.in_progress && !f.passes).count() as i64;
+        let active_sessions = self.get_sessions()?.len() as i64;
+
+        let percentage = if total > 0 {
+            (completed as f64 / total as f64) * 100.0
+        } else {
+            0.0
+        };
+
+        Ok(Stats {
+            total,
+            completed,
+            in_progress,
+            percentage,
+            active_sessions,
+            dirty_projects: 0,
+        })
+    }
+
+    fn get_config(&self) -> Result<Config, StorageError> {
+        match self.db.get_cf(&self.cf(CF_CONFIG), CONFIG_KEY)? {
+            Some(bytes) => Ok(serde_json::from_slice(&bytes).unwrap_or_default()),
+            None => Ok(Config::default()),
+        }
+    }
+
+    fn save_config(&self, config: &Config) -> Result<(), StorageError> {
+        let value = serde_json::to_vec(config)?;
+        self.db.put_cf(&self.cf(CF_CONFIG), CONFIG_KEY, value)?;
+        Ok(())
+    }
+
+    fn get_projects(&self) -> Result<Vec<String>, StorageError> {
+        let mut projects: Vec<String> = self
+            .db
+            .iterator_cf(&self.cf(CF_FEATURES), IteratorMode::Start)
+            .filter_map(|row| row.ok())
+            .filter_map(|(_, value)| serde_json::from_slice::<Feature>(&value).ok())
+            .map(|f| f.project_dir)
+            .collect();
+
+        projects.sort();
+        projects.dedup();
+        Ok(projects)
+    }
+}
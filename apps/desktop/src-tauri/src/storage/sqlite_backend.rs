@@ -0,0 +1,581 @@
+//! The default [`Storage`] backend: an r2d2 pool of SQLite connections
+//! opened in WAL journal mode, so concurrent readers (dashboard queries)
+//! aren't blocked behind a writer (hook event ingestion) the way a single
+//! shared connection would block them.
+
+use super::migrations;
+use super::{Storage, StorageError};
+use crate::db::{AgentEvent, Config, Feature, Session, Stats};
+use crate::hlc::{generate_node_id, physical_now_ms, Hlc};
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// How long a connection waits on SQLite's lock before giving up with
+/// `SQLITE_BUSY`, given to every pooled connection via `busy_timeout`.
+const BUSY_TIMEOUT_MS: u32 = 5_000;
+
+pub struct SqliteBackend {
+    pool: Pool<SqliteConnectionManager>,
+    /// This node's Hybrid Logical Clock, advanced on every feature sync.
+    /// See `hlc::Hlc` and `Storage::sync_features`.
+    hlc: Mutex<Hlc>,
+}
+
+impl SqliteBackend {
+    pub fn open(path: &Path) -> Result<Self, StorageError> {
+        let manager = SqliteConnectionManager::file(path).with_init(|conn| {
+            conn.execute_batch(&format!(
+                "PRAGMA journal_mode = WAL; PRAGMA busy_timeout = {BUSY_TIMEOUT_MS};"
+            ))?;
+            Ok(())
+        });
+
+        let pool = Pool::builder().build(manager)?;
+
+        {
+            let mut conn = pool.get()?;
+            migrations::apply(&mut conn)?;
+        }
+
+        let node_id = {
+            let conn = pool.get()?;
+            Self::local_node_id(&conn)?
+        };
+
+        Ok(Self {
+            pool,
+            hlc: Mutex::new(Hlc::zero(node_id)),
+        })
+    }
+
+    /// The schema version the database is currently at, per
+    /// `PRAGMA user_version`. Matches `migrations::CURRENT_SCHEMA_VERSION`
+    /// once `open` has returned successfully.
+    pub fn schema_version(&self) -> Result<u32, StorageError> {
+        let conn = self.pool.get()?;
+        let version: u32 = conn.query_row("PRAGMA user_version", [], |r| r.get(0))?;
+        Ok(version)
+    }
+
+    /// This machine's stable HLC node id, generating and persisting one in
+    /// the `config` table on first use so it survives restarts.
+    fn local_node_id(conn: &Connection) -> Result<String, StorageError> {
+        let existing: Option<String> = conn
+            .query_row(
+                "SELECT value FROM config WHERE key = 'hlc_node_id'",
+                [],
+                |r| r.get(0),
+            )
+            .ok();
+
+        if let Some(id) = existing {
+            return Ok(id);
+        }
+
+        let id = generate_node_id();
+        conn.execute(
+            "INSERT OR REPLACE INTO config (key, value) VALUES ('hlc_node_id', ?1)",
+            [&id],
+        )?;
+        Ok(id)
+    }
+}
+
+impl Storage for SqliteBackend {
+    fn schema_version(&self) -> Result<u32, StorageError> {
+        SqliteBackend::schema_version(self)
+    }
+
+    fn insert_event(&self, event: &AgentEvent) -> Result<i64, StorageError> {
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT INTO events (event_type, source_agent, session_id, project_dir, tool_name, payload, feature_id, tool_use_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                event.event_type,
+                event.source_agent,
+                event.session_id,
+                event.project_dir,
+                event.tool_name,
+                event.payload,
+                event.feature_id,
+                event.tool_use_id,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    fn get_events(&self, limit: i64) -> Result<Vec<AgentEvent>, StorageError> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, event_type, source_agent, session_id, project_dir, tool_name, payload, feature_id, tool_use_id, created_at
+             FROM events ORDER BY created_at DESC LIMIT ?1",
+        )?;
+
+        let events = stmt
+            .query_map([limit], |row| {
+                Ok(AgentEvent {
+                    id: Some(row.get(0)?),
+                    event_type: row.get(1)?,
+                    source_agent: row.get(2)?,
+                    session_id: row.get(3)?,
+                    project_dir: row.get(4)?,
+                    tool_name: row.get(5)?,
+                    payload: row.get(6)?,
+                    feature_id: row.get(7)?,
+                    tool_use_id: row.get(8)?,
+                    created_at: row.get(9)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(events)
+    }
+
+    fn get_events_by_feature(
+        &self,
+        feature_id: &str,
+        limit: i64,
+    ) -> Result<Vec<AgentEvent>, StorageError> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, event_type, source_agent, session_id, project_dir, tool_name, payload, feature_id, tool_use_id, created_at
+             FROM events WHERE feature_id = ?1 ORDER BY created_at DESC LIMIT ?2",
+        )?;
+
+        let events = stmt
+            .query_map(params![feature_id, limit], |row| {
+                Ok(AgentEvent {
+                    id: Some(row.get(0)?),
+                    event_type: row.get(1)?,
+                    source_agent: row.get(2)?,
+                    session_id: row.get(3)?,
+                    project_dir: row.get(4)?,
+                    tool_name: row.get(5)?,
+                    payload: row.get(6)?,
+                    feature_id: row.get(7)?,
+                    tool_use_id: row.get(8)?,
+                    created_at: row.get(9)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(events)
+    }
+
+    fn get_events_page(
+        &self,
+        before_id: Option<i64>,
+        event_type: Option<&str>,
+        source_agent: Option<&str>,
+        session_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<AgentEvent>, StorageError> {
+        let conn = self.pool.get()?;
+
+        let mut clauses = Vec::new();
+        let mut values: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+        if let Some(id) = before_id {
+            clauses.push("id < ?");
+            values.push(Box::new(id));
+        }
+        if let Some(v) = event_type {
+            clauses.push("event_type = ?");
+            values.push(Box::new(v.to_string()));
+        }
+        if let Some(v) = source_agent {
+            clauses.push("source_agent = ?");
+            values.push(Box::new(v.to_string()));
+        }
+        if let Some(v) = session_id {
+            clauses.push("session_id = ?");
+            values.push(Box::new(v.to_string()));
+        }
+
+        let where_clause = if clauses.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", clauses.join(" AND "))
+        };
+
+        let sql = format!(
+            "SELECT id, event_type, source_agent, session_id, project_dir, tool_name, payload, feature_id, tool_use_id, created_at
+             FROM events {where_clause} ORDER BY id DESC LIMIT ?"
+        );
+        values.push(Box::new(limit));
+
+        let mut stmt = conn.prepare(&sql)?;
+        let bound: Vec<&dyn rusqlite::ToSql> = values.iter().map(|v| v.as_ref()).collect();
+
+        let events = stmt
+            .query_map(bound.as_slice(), |row| {
+                Ok(AgentEvent {
+                    id: Some(row.get(0)?),
+                    event_type: row.get(1)?,
+                    source_agent: row.get(2)?,
+                    session_id: row.get(3)?,
+                    project_dir: row.get(4)?,
+                    tool_name: row.get(5)?,
+                    payload: row.get(6)?,
+                    feature_id: row.get(7)?,
+                    tool_use_id: row.get(8)?,
+                    created_at: row.get(9)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(events)
+    }
+
+    fn get_unlinked_events(
+        &self,
+        project_dir: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<AgentEvent>, StorageError> {
+        let conn = self.pool.get()?;
+
+        let mut stmt;
+        let events = if let Some(dir) = project_dir {
+            stmt = conn.prepare(
+                "SELECT id, event_type, source_agent, session_id, project_dir, tool_name, payload, feature_id, tool_use_id, created_at
+                 FROM events WHERE feature_id IS NULL AND project_dir = ?1 ORDER BY id DESC LIMIT ?2",
+            )?;
+            stmt.query_map(params![dir, limit], |row| {
+                Ok(AgentEvent {
+                    id: Some(row.get(0)?),
+                    event_type: row.get(1)?,
+                    source_agent: row.get(2)?,
+                    session_id: row.get(3)?,
+                    project_dir: row.get(4)?,
+                    tool_name: row.get(5)?,
+                    payload: row.get(6)?,
+                    feature_id: row.get(7)?,
+                    tool_use_id: row.get(8)?,
+                    created_at: row.get(9)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?
+        } else {
+            stmt = conn.prepare(
+                "SELECT id, event_type, source_agent, session_id, project_dir, tool_name, payload, feature_id, tool_use_id, created_at
+                 FROM events WHERE feature_id IS NULL ORDER BY id DESC LIMIT ?1",
+            )?;
+            stmt.query_map([limit], |row| {
+                Ok(AgentEvent {
+                    id: Some(row.get(0)?),
+                    event_type: row.get(1)?,
+                    source_agent: row.get(2)?,
+                    session_id: row.get(3)?,
+                    project_dir: row.get(4)?,
+                    tool_name: row.get(5)?,
+                    payload: row.get(6)?,
+                    feature_id: row.get(7)?,
+                    tool_use_id: row.get(8)?,
+                    created_at: row.get(9)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?
+        };
+
+        Ok(events)
+    }
+
+    fn link_event_to_feature(&self, event_id: i64, feature_id: &str) -> Result<bool, StorageError> {
+        let conn = self.pool.get()?;
+        let updated = conn.execute(
+            "UPDATE events SET feature_id = ?1 WHERE id = ?2",
+            params![feature_id, event_id],
+        )?;
+        Ok(updated > 0)
+    }
+
+    fn annotate_tool_call(
+        &self,
+        event_id: i64,
+        is_error: bool,
+        result_preview: &str,
+        duration_ms: i64,
+    ) -> Result<(), StorageError> {
+        let conn = self.pool.get()?;
+
+        let payload: Option<String> = conn
+            .query_row(
+                "SELECT payload FROM events WHERE id = ?1",
+                [event_id],
+                |r| r.get(0),
+            )
+            .optional()?
+            .flatten();
+
+        let mut merged: serde_json::Value = payload
+            .as_deref()
+            .and_then(|p| serde_json::from_str(p).ok())
+            .unwrap_or_else(|| serde_json::json!({}));
+        merged["isError"] = serde_json::json!(is_error);
+        merged["resultPreview"] = serde_json::json!(result_preview);
+        merged["durationMs"] = serde_json::json!(duration_ms);
+
+        conn.execute(
+            "UPDATE events SET payload = ?1 WHERE id = ?2",
+            params![merged.to_string(), event_id],
+        )?;
+
+        Ok(())
+    }
+
+    fn sync_features(&self, project_dir: &str, features: Vec<Feature>) -> Result<(), StorageError> {
+        let conn = self.pool.get()?;
+
+        for feature in features {
+            // A feature with no `hlc` is a local observation (e.g. the file
+            // watcher re-reading feature_list.json) — stamp it with this
+            // node's own advancing clock. One that already carries an `hlc`
+            // is being merged in from another machine's sync, so fold its
+            // timestamp into ours to keep this node's clock correctly
+            // advanced (`merge_remote` requires that regardless of which
+            // side wins the conflict check below).
+            let winning_hlc = {
+                let mut clock = self.hlc.lock().unwrap();
+                match &feature.hlc {
+                    Some(remote) => {
+                        *clock = clock.merge_remote(remote.l, remote.c, physical_now_ms());
+                        remote.clone()
+                    }
+                    None => {
+                        *clock = clock.tick_local(physical_now_ms());
+                        clock.clone()
+                    }
+                }
+            };
+
+            let stored: Option<(i64, i64, String)> = conn
+                .query_row(
+                    "SELECT hlc_l, hlc_c, hlc_node FROM features WHERE id = ?1",
+                    [&feature.id],
+                    |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+                )
+                .ok();
+
+            if let Some((l, c, node)) = &stored {
+                if (*l, *c, node.as_str()) >= winning_hlc.as_tuple() {
+                    // The stored row's clock already dominates (or exactly
+                    // matches) the incoming one — an idempotent no-op, and
+                    // never a regression from a stale/out-of-order write.
+                    continue;
+                }
+            }
+
+            let steps_json = feature
+                .steps
+                .as_ref()
+                .map(|s| serde_json::to_string(s).unwrap_or_default());
+
+            conn.execute(
+                "INSERT OR REPLACE INTO features (id, project_dir, description, category, passes, in_progress, agent, steps, updated_at, hlc_l, hlc_c, hlc_node)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, datetime('now'), ?9, ?10, ?11)",
+                params![
+                    feature.id,
+                    project_dir,
+                    feature.description,
+                    feature.category,
+                    feature.passes,
+                    feature.in_progress,
+                    feature.agent,
+                    steps_json,
+                    winning_hlc.l,
+                    winning_hlc.c,
+                    winning_hlc.node_id,
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn get_features(&self, project_dir: Option<&str>) -> Result<Vec<Feature>, StorageError> {
+        let conn = self.pool.get()?;
+
+        fn parse_steps(steps_json: Option<String>) -> Option<Vec<String>> {
+            steps_json.and_then(|s| serde_json::from_str(&s).ok())
+        }
+
+        if let Some(dir) = project_dir {
+            let mut stmt = conn.prepare(
+                "SELECT id, project_dir, description, category, passes, in_progress, agent, steps, updated_at
+                 FROM features WHERE project_dir = ?1 ORDER BY id",
+            )?;
+
+            let features = stmt
+                .query_map([dir], |row| {
+                    Ok(Feature {
+                        id: row.get(0)?,
+                        project_dir: row.get(1)?,
+                        description: row.get(2)?,
+                        category: row.get(3)?,
+                        passes: row.get(4)?,
+                        in_progress: row.get(5)?,
+                        agent: row.get(6)?,
+                        steps: parse_steps(row.get(7)?),
+                        updated_at: row.get(8)?,
+                        git_status: None,
+                        hlc: None,
+                    })
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(features)
+        } else {
+            let mut stmt = conn.prepare(
+                "SELECT id, project_dir, description, category, passes, in_progress, agent, steps, updated_at
+                 FROM features ORDER BY project_dir, id",
+            )?;
+
+            let features = stmt
+                .query_map([], |row| {
+                    Ok(Feature {
+                        id: row.get(0)?,
+                        project_dir: row.get(1)?,
+                        description: row.get(2)?,
+                        category: row.get(3)?,
+                        passes: row.get(4)?,
+                        in_progress: row.get(5)?,
+                        agent: row.get(6)?,
+                        steps: parse_steps(row.get(7)?),
+                        updated_at: row.get(8)?,
+                        git_status: None,
+                        hlc: None,
+                    })
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(features)
+        }
+    }
+
+    fn get_sessions(&self) -> Result<Vec<Session>, StorageError> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT session_id, source_agent, project_dir, started_at, last_activity, status
+             FROM sessions WHERE status = 'active' ORDER BY last_activity DESC",
+        )?;
+
+        let sessions = stmt
+            .query_map([], |row| {
+                Ok(Session {
+                    session_id: row.get(0)?,
+                    source_agent: row.get(1)?,
+                    project_dir: row.get(2)?,
+                    started_at: row.get(3)?,
+                    last_activity: row.get(4)?,
+                    status: row.get(5)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(sessions)
+    }
+
+    fn upsert_session(&self, session: &Session) -> Result<(), StorageError> {
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT OR REPLACE INTO sessions (session_id, source_agent, project_dir, started_at, last_activity, status)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                session.session_id,
+                session.source_agent,
+                session.project_dir,
+                session.started_at,
+                session.last_activity,
+                session.status,
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn update_session_status(&self, session_id: &str, status: &str) -> Result<(), StorageError> {
+        let conn = self.pool.get()?;
+        conn.execute(
+            "UPDATE sessions SET status = ?1, last_activity = datetime('now') WHERE session_id = ?2",
+            params![status, session_id],
+        )?;
+        Ok(())
+    }
+
+    fn get_stats(&self) -> Result<Stats, StorageError> {
+        let conn = self.pool.get()?;
+
+        let total: i64 = conn.query_row("SELECT COUNT(*) FROM features", [], |r| r.get(0))?;
+
+        let completed: i64 =
+            conn.query_row("SELECT COUNT(*) FROM features WHERE passes = 1", [], |r| {
+                r.get(0)
+            })?;
+
+        let in_progress: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM features WHERE in_progress = 1 AND passes = 0",
+            [],
+            |r| r.get(0),
+        )?;
+
+        let active_sessions: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM sessions WHERE status = 'active'",
+            [],
+            |r| r.get(0),
+        )?;
+
+        let percentage = if total > 0 {
+            (completed as f64 / total as f64) * 100.0
+        } else {
+            0.0
+        };
+
+        Ok(Stats {
+            total,
+            completed,
+            in_progress,
+            percentage,
+            active_sessions,
+            dirty_projects: 0,
+        })
+    }
+
+    fn get_config(&self) -> Result<Config, StorageError> {
+        let conn = self.pool.get()?;
+
+        let config_json: Option<String> = conn
+            .query_row("SELECT value FROM config WHERE key = 'main'", [], |r| {
+                r.get(0)
+            })
+            .ok();
+
+        match config_json {
+            Some(json) => Ok(serde_json::from_str(&json).unwrap_or_default()),
+            None => Ok(Config::default()),
+        }
+    }
+
+    fn save_config(&self, config: &Config) -> Result<(), StorageError> {
+        let conn = self.pool.get()?;
+        let json = serde_json::to_string(config)?;
+        conn.execute(
+            "INSERT OR REPLACE INTO config (key, value) VALUES ('main', ?1)",
+            [json],
+        )?;
+        Ok(())
+    }
+
+    fn get_projects(&self) -> Result<Vec<String>, StorageError> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare("SELECT DISTINCT project_dir FROM features ORDER BY project_dir")?;
+
+        let projects = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+
+        Ok(projects)
+    }
+}
@@ -0,0 +1,363 @@
+//! Typed models for a line of a Claude Code transcript JSONL file.
+//!
+//! Claude's transcript schema isn't contractually stable, so [`TranscriptEntry`]
+//! is split the way a checked/dynamic boundary usually is: the known shapes
+//! (`user`/`assistant`/`result`) deserialize into real structs via a private
+//! internally-tagged [`TaggedEntry`], and anything that doesn't match falls
+//! back to [`TranscriptEntry::Dynamic`] holding the raw JSON, so an unknown
+//! or changed message type still yields a best-effort preview instead of the
+//! watcher silently dropping it.
+
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+#[derive(Debug, Deserialize)]
+pub struct UserEntry {
+    pub message: UserMessage,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UserMessage {
+    #[serde(default)]
+    pub content: Vec<ContentBlock>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AssistantEntry {
+    pub message: AssistantMessage,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AssistantMessage {
+    #[serde(default)]
+    pub content: Vec<ContentBlock>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResultEntry {
+    #[serde(default)]
+    pub is_error: bool,
+    pub tool_use_id: Option<String>,
+    #[serde(default)]
+    pub content: Option<ContentOrBlocks>,
+    pub output: Option<String>,
+}
+
+/// A transcript `content` field that's either a plain string or an array of
+/// content blocks, depending on which part of the schema produced it.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum ContentOrBlocks {
+    Text(String),
+    Blocks(Vec<ContentBlock>),
+}
+
+impl ContentOrBlocks {
+    fn preview(&self, limit: usize) -> String {
+        match self {
+            ContentOrBlocks::Text(text) => text.chars().take(limit).collect(),
+            ContentOrBlocks::Blocks(blocks) => blocks
+                .iter()
+                .filter_map(|b| match b {
+                    ContentBlock::Text { text } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+                .chars()
+                .take(limit)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentBlock {
+    Text {
+        text: String,
+    },
+    ToolUse {
+        /// The block's own id, echoed back as `tool_use_id` on the matching
+        /// `ToolResult`/`ResultEntry`. Lets a later result be joined back to
+        /// the call that produced it — see `tool_use_id`.
+        id: Option<String>,
+        name: String,
+        #[serde(default)]
+        input: Value,
+    },
+    ToolResult {
+        tool_use_id: Option<String>,
+        #[serde(default)]
+        is_error: bool,
+        #[serde(default)]
+        content: Option<ContentOrBlocks>,
+    },
+    Image,
+    Thinking {
+        thinking: String,
+    },
+}
+
+/// The known transcript entry shapes, tagged on `type`. Kept private —
+/// callers only see [`TranscriptEntry`], which folds a failed match here
+/// into [`TranscriptEntry::Dynamic`] rather than propagating the error.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum TaggedEntry {
+    User(UserEntry),
+    Assistant(AssistantEntry),
+    Result(ResultEntry),
+}
+
+#[derive(Debug)]
+pub enum TranscriptEntry {
+    User(UserEntry),
+    Assistant(AssistantEntry),
+    Result(ResultEntry),
+    /// A `type` we don't have a typed shape for yet, or a line that matched
+    /// `type` but not the rest of the expected structure. Holds the raw
+    /// parsed JSON so callers can still take a best-effort preview.
+    Dynamic(Value),
+}
+
+impl<'de> Deserialize<'de> for TranscriptEntry {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        Ok(match serde_json::from_value::<TaggedEntry>(value.clone()) {
+            Ok(TaggedEntry::User(entry)) => TranscriptEntry::User(entry),
+            Ok(TaggedEntry::Assistant(entry)) => TranscriptEntry::Assistant(entry),
+            Ok(TaggedEntry::Result(entry)) => TranscriptEntry::Result(entry),
+            Err(_) => TranscriptEntry::Dynamic(value),
+        })
+    }
+}
+
+/// The `tool_use_id` linking a `ToolUse` call to its later `ToolResult`, if
+/// `entry` carries one. A `ToolUse` block's own id and a `ToolResult`'s
+/// `tool_use_id` are the same value by convention, so callers can use this
+/// to join a call event to the result event that completes it.
+pub fn tool_use_id(entry: &TranscriptEntry) -> Option<String> {
+    match entry {
+        TranscriptEntry::User(entry) => entry.message.content.iter().find_map(|b| match b {
+            ContentBlock::ToolResult { tool_use_id, .. } => tool_use_id.clone(),
+            _ => None,
+        }),
+        TranscriptEntry::Assistant(entry) => entry.message.content.iter().find_map(|b| match b {
+            ContentBlock::ToolUse { id, .. } => id.clone(),
+            _ => None,
+        }),
+        TranscriptEntry::Result(entry) => entry.tool_use_id.clone(),
+        TranscriptEntry::Dynamic(_) => None,
+    }
+}
+
+/// Derive a `(tool_name, payload_json)` preview pair from a parsed
+/// transcript entry, mirroring what the dashboard's event feed shows for a
+/// live `AgentEvent`.
+pub fn summarize(entry: TranscriptEntry) -> (Option<String>, Option<String>) {
+    match entry {
+        TranscriptEntry::User(entry) => summarize_user(entry),
+        TranscriptEntry::Assistant(entry) => summarize_assistant(entry),
+        TranscriptEntry::Result(entry) => summarize_result(entry),
+        TranscriptEntry::Dynamic(value) => summarize_dynamic(value),
+    }
+}
+
+fn summarize_user(entry: UserEntry) -> (Option<String>, Option<String>) {
+    for block in &entry.message.content {
+        match block {
+            ContentBlock::Text { text } => {
+                let preview: String = text.chars().take(500).collect();
+                if !preview.is_empty() {
+                    let payload = serde_json::json!({
+                        "messageType": "user",
+                        "preview": preview,
+                    });
+                    return (Some("UserMessage".to_string()), Some(payload.to_string()));
+                }
+            }
+            ContentBlock::ToolResult {
+                tool_use_id,
+                is_error,
+                content,
+            } => {
+                let preview = content.as_ref().map(|c| c.preview(300)).unwrap_or_default();
+                let payload = serde_json::json!({
+                    "messageType": "tool_result",
+                    "toolUseId": tool_use_id.as_deref().unwrap_or("unknown"),
+                    "isError": is_error,
+                    "preview": preview,
+                });
+                return (Some("ToolResult".to_string()), Some(payload.to_string()));
+            }
+            ContentBlock::Image => {
+                let payload = serde_json::json!({
+                    "messageType": "image",
+                    "preview": "\u{1F4F7} Image uploaded",
+                });
+                return (Some("Image".to_string()), Some(payload.to_string()));
+            }
+            _ => {}
+        }
+    }
+
+    let payload = serde_json::json!({
+        "messageType": "user",
+        "preview": "",
+    });
+    (Some("UserMessage".to_string()), Some(payload.to_string()))
+}
+
+fn summarize_assistant(entry: AssistantEntry) -> (Option<String>, Option<String>) {
+    for block in &entry.message.content {
+        match block {
+            ContentBlock::ToolUse { name, input, .. } => {
+                let mut payload = serde_json::json!({
+                    "messageType": "tool_use",
+                    "tool": name,
+                });
+
+                // Add tool-specific input details
+                match name.as_str() {
+                    "Bash" => {
+                        if let Some(cmd) = input["command"].as_str() {
+                            payload["command"] =
+                                serde_json::json!(cmd.chars().take(500).collect::<String>());
+                        }
+                        if let Some(desc) = input["description"].as_str() {
+                            payload["description"] = serde_json::json!(desc);
+                        }
+                    }
+                    "Edit" => {
+                        if let Some(fp) = input["file_path"].as_str() {
+                            payload["filePath"] = serde_json::json!(fp);
+                        }
+                        if let Some(old) = input["old_string"].as_str() {
+                            payload["oldString"] =
+                                serde_json::json!(old.chars().take(200).collect::<String>());
+                        }
+                        if let Some(new) = input["new_string"].as_str() {
+                            payload["newString"] =
+                                serde_json::json!(new.chars().take(200).collect::<String>());
+                        }
+                    }
+                    "Write" => {
+                        if let Some(fp) = input["file_path"].as_str() {
+                            payload["filePath"] = serde_json::json!(fp);
+                        }
+                        if let Some(content) = input["content"].as_str() {
+                            payload["contentPreview"] =
+                                serde_json::json!(content.chars().take(200).collect::<String>());
+                        }
+                    }
+                    "Read" => {
+                        if let Some(fp) = input["file_path"].as_str() {
+                            payload["filePath"] = serde_json::json!(fp);
+                        }
+                        if let Some(offset) = input["offset"].as_i64() {
+                            payload["offset"] = serde_json::json!(offset);
+                        }
+                        if let Some(limit) = input["limit"].as_i64() {
+                            payload["limit"] = serde_json::json!(limit);
+                        }
+                    }
+                    "Grep" => {
+                        if let Some(pattern) = input["pattern"].as_str() {
+                            payload["pattern"] = serde_json::json!(pattern);
+                        }
+                        if let Some(path) = input["path"].as_str() {
+                            payload["path"] = serde_json::json!(path);
+                        }
+                    }
+                    "Glob" => {
+                        if let Some(pattern) = input["pattern"].as_str() {
+                            payload["pattern"] = serde_json::json!(pattern);
+                        }
+                        if let Some(path) = input["path"].as_str() {
+                            payload["path"] = serde_json::json!(path);
+                        }
+                    }
+                    "Task" => {
+                        if let Some(desc) = input["description"].as_str() {
+                            payload["taskDescription"] = serde_json::json!(desc);
+                        }
+                        if let Some(agent) = input["subagent_type"].as_str() {
+                            payload["subagentType"] = serde_json::json!(agent);
+                        }
+                    }
+                    _ => {
+                        // For other tools, include a preview of the input
+                        let input_str = input.to_string();
+                        if input_str.len() > 2 {
+                            // More than just "{}"
+                            payload["inputPreview"] =
+                                serde_json::json!(input_str.chars().take(300).collect::<String>());
+                        }
+                    }
+                }
+
+                return (Some(name.clone()), Some(payload.to_string()));
+            }
+            ContentBlock::Text { text } => {
+                let preview: String = text.chars().take(500).collect();
+                let payload = serde_json::json!({
+                    "messageType": "assistant",
+                    "preview": preview,
+                });
+                return (Some("Response".to_string()), Some(payload.to_string()));
+            }
+            ContentBlock::Thinking { thinking } => {
+                let preview: String = thinking.chars().take(500).collect();
+                let payload = serde_json::json!({
+                    "messageType": "thinking",
+                    "preview": preview,
+                });
+                return (Some("Thinking".to_string()), Some(payload.to_string()));
+            }
+            _ => {}
+        }
+    }
+
+    (Some("Assistant".to_string()), None)
+}
+
+fn summarize_result(entry: ResultEntry) -> (Option<String>, Option<String>) {
+    let preview = entry
+        .content
+        .as_ref()
+        .map(|c| c.preview(300))
+        .or_else(|| entry.output.as_ref().map(|o| o.chars().take(300).collect()))
+        .unwrap_or_default();
+
+    let payload = serde_json::json!({
+        "messageType": "tool_result",
+        "toolUseId": entry.tool_use_id.as_deref().unwrap_or("unknown"),
+        "isError": entry.is_error,
+        "preview": preview,
+    });
+
+    (Some("ToolResult".to_string()), Some(payload.to_string()))
+}
+
+/// Best-effort preview for a `type` we don't have a typed shape for, so
+/// schema drift degrades gracefully instead of producing `(None, None)`.
+fn summarize_dynamic(value: Value) -> (Option<String>, Option<String>) {
+    let msg_type = value
+        .get("type")
+        .and_then(|t| t.as_str())
+        .unwrap_or("unknown")
+        .to_string();
+
+    let preview: String = value.to_string().chars().take(300).collect();
+
+    let payload = serde_json::json!({
+        "messageType": msg_type,
+        "preview": preview,
+    });
+
+    (Some("Unknown".to_string()), Some(payload.to_string()))
+}
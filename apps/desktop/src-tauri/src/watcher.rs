@@ -1,32 +1,171 @@
-use crate::db::{AgentEvent, DbState, Feature};
+use crate::agent_source::{AgentSource, ClaudeCodeSource};
+use crate::correlation::ToolCallTracker;
+use crate::db::{AgentEvent, Database, DbState, Feature};
+use crate::embeddings::EmbeddingQueue;
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
-use notify_debouncer_mini::{new_debouncer, DebouncedEvent};
-use std::collections::HashSet;
+use notify_debouncer_mini::{new_debouncer, DebouncedEvent, Debouncer};
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::sync::broadcast;
 
+/// Live handle to the filesystem watcher, shared as Tauri-managed state so
+/// commands can add or remove watched project directories at runtime
+/// instead of only at app boot.
+pub struct ProjectWatcher {
+    debouncer: Mutex<Debouncer<RecommendedWatcher>>,
+    watched_dirs: Mutex<HashSet<PathBuf>>,
+    /// Last-read byte offset per transcript file, so a debounced change
+    /// event only re-reads the bytes appended since the previous read
+    /// instead of the whole file.
+    transcript_offsets: Mutex<HashMap<PathBuf, u64>>,
+    /// Registered coding-agent transcript formats, tried in order by
+    /// `matches` against each changed path.
+    sources: Vec<Box<dyn AgentSource + Send + Sync>>,
+    /// Joins each session's outstanding tool calls to their results. See
+    /// `correlation::ToolCallTracker`.
+    tool_calls: ToolCallTracker,
+    /// Chunks and embeds parsed transcript text on its own thread. See
+    /// `embeddings::EmbeddingQueue`.
+    embeddings: Arc<EmbeddingQueue>,
+}
+
+impl ProjectWatcher {
+    /// Read the complete lines appended to `path` since the last call,
+    /// advancing the stored offset. A trailing partial line (no `\n` yet)
+    /// is left unconsumed for the next read. If the file has shrunk below
+    /// the stored offset (truncated or rotated), the offset resets to 0
+    /// and the file is read from the start.
+    fn read_new_transcript_lines(&self, path: &Path) -> Vec<String> {
+        let mut offsets = self.transcript_offsets.lock().unwrap();
+
+        let mut file = match File::open(path) {
+            Ok(f) => f,
+            Err(_) => return Vec::new(),
+        };
+        let len = match file.metadata() {
+            Ok(m) => m.len(),
+            Err(_) => return Vec::new(),
+        };
+
+        let stored_offset = offsets.get(path).copied().unwrap_or(0);
+        let start = if len < stored_offset {
+            0
+        } else {
+            stored_offset
+        };
+
+        if file.seek(SeekFrom::Start(start)).is_err() {
+            return Vec::new();
+        }
+        let mut appended = String::new();
+        if file.read_to_string(&mut appended).is_err() {
+            return Vec::new();
+        }
+
+        // Only count bytes belonging to complete (newline-terminated)
+        // lines as consumed; an in-progress line is re-read next time.
+        let mut lines = Vec::new();
+        let mut consumed = 0usize;
+        for chunk in appended.split_inclusive('\n') {
+            if !chunk.ends_with('\n') {
+                break;
+            }
+            consumed += chunk.len();
+            let line = chunk.trim_end_matches(['\n', '\r']);
+            if !line.trim().is_empty() {
+                lines.push(line.to_string());
+            }
+        }
+
+        offsets.insert(path.to_path_buf(), start + consumed as u64);
+        lines
+    }
+
+    /// The first registered source that claims `path`, if any.
+    fn source_for(&self, path: &Path) -> Option<&(dyn AgentSource + Send + Sync)> {
+        self.sources
+            .iter()
+            .map(|s| s.as_ref())
+            .find(|s| s.matches(path))
+    }
+
+    /// Start watching `dir` (a project directory) for `feature_list.json`
+    /// changes. Idempotent: re-watching an already-watched directory is a
+    /// no-op.
+    pub fn watch_project(&self, dir: &Path) -> notify::Result<()> {
+        let mut watched = self.watched_dirs.lock().unwrap();
+        if watched.contains(dir) {
+            return Ok(());
+        }
+
+        self.debouncer
+            .lock()
+            .unwrap()
+            .watcher()
+            .watch(dir, RecursiveMode::NonRecursive)?;
+        watched.insert(dir.to_path_buf());
+        tracing::info!("Watching project: {:?}", dir);
+        Ok(())
+    }
+
+    /// Stop watching `dir`. A no-op if it wasn't being watched.
+    pub fn unwatch_project(&self, dir: &Path) -> notify::Result<()> {
+        let mut watched = self.watched_dirs.lock().unwrap();
+        if !watched.remove(dir) {
+            return Ok(());
+        }
+
+        self.debouncer.lock().unwrap().watcher().unwatch(dir)?;
+        tracing::info!("Stopped watching project: {:?}", dir);
+        Ok(())
+    }
+}
+
+pub struct WatcherState(pub Arc<ProjectWatcher>);
+
 pub fn start_watching(
     app: tauri::AppHandle,
     event_tx: Arc<broadcast::Sender<AgentEvent>>,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let home = dirs::home_dir().ok_or("Could not find home directory")?;
-
-    // Paths to watch
-    let claude_projects = home.join(".claude/projects");
+    embeddings: Arc<EmbeddingQueue>,
+) -> Result<WatcherState, Box<dyn std::error::Error + Send + Sync>> {
+    // Registered coding-agent transcript formats. Add an entry here (and a
+    // matching `AgentSource` impl in `agent_source`) to watch another
+    // agent's transcripts without touching the dispatch loop below.
+    let sources: Vec<Box<dyn AgentSource + Send + Sync>> = vec![Box::new(
+        ClaudeCodeSource::new().ok_or("Could not find home directory")?,
+    )];
 
     let (tx, rx) = std::sync::mpsc::channel();
 
-    let mut debouncer = new_debouncer(Duration::from_millis(500), tx)?;
-
-    // Watch Claude Code transcripts
-    if claude_projects.exists() {
-        debouncer
-            .watcher()
-            .watch(&claude_projects, RecursiveMode::Recursive)?;
-        tracing::info!("Watching Claude projects: {:?}", claude_projects);
+    let debouncer = new_debouncer(Duration::from_millis(500), tx)?;
+
+    let watcher = Arc::new(ProjectWatcher {
+        debouncer: Mutex::new(debouncer),
+        watched_dirs: Mutex::new(HashSet::new()),
+        transcript_offsets: Mutex::new(HashMap::new()),
+        sources,
+        tool_calls: ToolCallTracker::new(),
+        embeddings,
+    });
+
+    // Watch each source's transcript roots
+    for source in &watcher.sources {
+        for root in source.watch_roots() {
+            if root.exists() {
+                watcher
+                    .debouncer
+                    .lock()
+                    .unwrap()
+                    .watcher()
+                    .watch(&root, RecursiveMode::Recursive)?;
+                tracing::info!("Watching agent transcripts: {:?}", root);
+            }
+        }
     }
 
     // Load config and watch configured project directories
@@ -36,10 +175,7 @@ pub fn start_watching(
             let feature_file = PathBuf::from(project).join("feature_list.json");
             if let Some(parent) = feature_file.parent() {
                 if parent.exists() {
-                    let _ = debouncer
-                        .watcher()
-                        .watch(parent, RecursiveMode::NonRecursive);
-                    tracing::info!("Watching project: {:?}", parent);
+                    let _ = watcher.watch_project(parent);
                 }
             }
         }
@@ -47,58 +183,52 @@ pub fn start_watching(
 
     tracing::info!("File watcher started");
 
-    for result in rx {
-        match result {
-            Ok(events) => {
-                for event in events {
-                    handle_file_event(&app, &event_tx, &event.path);
+    let watcher_loop = Arc::clone(&watcher);
+    std::thread::spawn(move || {
+        // Keep the debouncer (and therefore its underlying OS watches) alive
+        // for as long as this loop runs.
+        let _watcher = watcher_loop;
+
+        for result in rx {
+            match result {
+                Ok(events) => {
+                    for event in events {
+                        handle_file_event(&app, &watcher_loop, &event_tx, &event.path);
+                    }
                 }
+                Err(e) => tracing::error!("Watch error: {:?}", e),
             }
-            Err(e) => tracing::error!("Watch error: {:?}", e),
         }
-    }
+    });
 
-    Ok(())
+    Ok(WatcherState(watcher))
 }
 
 fn handle_file_event(
     app: &tauri::AppHandle,
+    watcher: &Arc<ProjectWatcher>,
     event_tx: &broadcast::Sender<AgentEvent>,
     path: &Path,
 ) {
-    let path_str = path.to_string_lossy();
-
-    // Handle transcript files (Claude Code sessions)
-    if path_str.ends_with(".jsonl") && path_str.contains(".claude/projects") {
-        handle_transcript_change(app, event_tx, path);
+    // Dispatch to whichever registered source recognizes this path.
+    if let Some(source) = watcher.source_for(path) {
+        handle_transcript_change(app, watcher, source, event_tx, path);
     }
 
-    // Handle feature_list.json changes
-    if path_str.ends_with("feature_list.json") {
+    // Handle feature manifest changes (feature_list.{json,toml,yaml,yml})
+    if crate::feature_manifest::is_manifest_path(path) {
         handle_feature_list_change(app, event_tx, path);
     }
 }
 
 fn handle_transcript_change(
     app: &tauri::AppHandle,
+    watcher: &Arc<ProjectWatcher>,
+    source: &(dyn AgentSource + Send + Sync),
     event_tx: &broadcast::Sender<AgentEvent>,
     path: &Path,
 ) {
-    // Extract project dir from transcript path
-    // Path format: ~/.claude/projects/{encoded-project}/session.jsonl
-    let encoded_project = path
-        .parent()
-        .and_then(|p| p.file_name())
-        .map(|s| s.to_string_lossy().to_string())
-        .unwrap_or_else(|| "unknown".to_string());
-
-    // Decode the project path (Claude uses - as path separator)
-    // e.g., "-Users-shakes-DevProjects-agentkanban" -> "/Users/shakes/DevProjects/agentkanban"
-    let project_dir = if encoded_project.starts_with('-') {
-        encoded_project.replace('-', "/")
-    } else {
-        encoded_project
-    };
+    let project_dir = source.decode_project_dir(path);
 
     // Parse session ID from filename
     let session_id = path
@@ -109,274 +239,157 @@ fn handle_transcript_change(
     // Find active feature in this project
     let feature_id = get_active_feature_id(&project_dir);
 
-    // Get last transcript entry for context
-    let (tool_name, payload) = get_last_transcript_entry(path);
-
-    let event = AgentEvent {
-        id: None,
-        event_type: "TranscriptUpdated".to_string(),
-        source_agent: "claude-code".to_string(),
-        session_id,
-        project_dir,
-        tool_name,
-        payload,
-        feature_id,
-        created_at: chrono::Utc::now().to_rfc3339(),
-    };
-
-    // Store in database
     let db: tauri::State<DbState> = app.state();
-    let _ = db.0.insert_event(&event);
 
-    // Broadcast to frontend
-    let _ = event_tx.send(event);
-}
+    // Only the bytes appended since the last read are parsed, and every
+    // complete line in the burst is emitted as its own event — not just
+    // the last one — so rapid tool-call/tool-result pairs within a single
+    // debounce window aren't collapsed into one preview.
+    for line in watcher.read_new_transcript_lines(path) {
+        let Some((tool_name, parsed)) = source.parse_entry(&line) else {
+            continue;
+        };
+        let is_result = tool_name == "ToolResult";
+
+        let event = AgentEvent {
+            id: None,
+            event_type: "TranscriptUpdated".to_string(),
+            source_agent: source.source_agent().to_string(),
+            session_id: session_id.clone(),
+            project_dir: project_dir.clone(),
+            tool_name: Some(tool_name.clone()),
+            payload: parsed.payload.clone(),
+            feature_id: feature_id.clone(),
+            tool_use_id: parsed.tool_use_id.clone(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+        };
+
+        let event_id = db.0.insert_event(&event).ok();
+
+        if let Some(text) = parsed
+            .payload
+            .as_deref()
+            .and_then(crate::embeddings::embeddable_text)
+        {
+            watcher.embeddings.enqueue(
+                &project_dir,
+                &session_id,
+                feature_id.as_deref(),
+                event_id,
+                &text,
+            );
+        }
 
-/// Parse the last entry from a transcript JSONL file
-fn get_last_transcript_entry(path: &Path) -> (Option<String>, Option<String>) {
-    let content = match std::fs::read_to_string(path) {
-        Ok(c) => c,
-        Err(_) => return (None, None),
-    };
+        let _ = event_tx.send(event);
+
+        let Some(tool_use_id) = parsed.tool_use_id else {
+            continue;
+        };
+
+        if is_result {
+            complete_tool_call(
+                watcher,
+                &db,
+                event_tx,
+                source,
+                &session_id,
+                &project_dir,
+                &tool_use_id,
+                parsed.payload.as_deref(),
+            );
+        } else if let Some(event_id) = event_id {
+            watcher
+                .tool_calls
+                .record_call(&session_id, &tool_use_id, event_id, &tool_name);
+        }
+    }
+}
 
-    // Get last non-empty line
-    let last_line = content.lines().filter(|l| !l.trim().is_empty()).last();
-    let last_line = match last_line {
-        Some(l) => l,
-        None => return (None, None),
-    };
+/// Join a `tool_result` back to the call it answers: annotate the
+/// originating event with the outcome and build the `ToolCallCompleted`
+/// event the frontend can render as one logical step instead of two flat
+/// entries. Returns `None` if `tool_use_id` has no matching outstanding call
+/// (e.g. it arrived before the watcher started). Tauri-decoupled (plain
+/// `&Database`, no `State`/broadcast sender) so `replay::run_workload` can
+/// drive the same correlation logic as the live watcher — mirrors the
+/// `sync_feature_list` split below. Callers are responsible for inserting
+/// and broadcasting the returned event.
+pub(crate) fn resolve_tool_call(
+    db: &Database,
+    tool_calls: &ToolCallTracker,
+    source_agent: &str,
+    session_id: &str,
+    project_dir: &str,
+    tool_use_id: &str,
+    result_payload: Option<&str>,
+) -> Option<AgentEvent> {
+    let (call_event_id, call_tool_name, elapsed) = tool_calls.resolve(session_id, tool_use_id)?;
+
+    let result: serde_json::Value = result_payload
+        .and_then(|p| serde_json::from_str(p).ok())
+        .unwrap_or_else(|| serde_json::json!({}));
+    let is_error = result["isError"].as_bool().unwrap_or(false);
+    let preview = result["preview"].as_str().unwrap_or("");
+    let duration_ms = elapsed.as_millis() as i64;
+
+    let _ = db.annotate_tool_call(call_event_id, is_error, preview, duration_ms);
+
+    Some(AgentEvent {
+        id: None,
+        event_type: "ToolCallCompleted".to_string(),
+        source_agent: source_agent.to_string(),
+        session_id: session_id.to_string(),
+        project_dir: project_dir.to_string(),
+        tool_name: Some(call_tool_name),
+        payload: Some(
+            serde_json::json!({
+                "isError": is_error,
+                "preview": preview,
+                "durationMs": duration_ms,
+            })
+            .to_string(),
+        ),
+        feature_id: None,
+        tool_use_id: Some(tool_use_id.to_string()),
+        created_at: chrono::Utc::now().to_rfc3339(),
+    })
+}
 
-    // Parse as JSON
-    let entry: serde_json::Value = match serde_json::from_str(last_line) {
-        Ok(v) => v,
-        Err(_) => return (None, None),
+/// Join a `tool_result` back to the call it answers and emit the completion
+/// event over Tauri's broadcast channel. See [`resolve_tool_call`] for the
+/// decoupled correlation logic this wraps.
+fn complete_tool_call(
+    watcher: &Arc<ProjectWatcher>,
+    db: &tauri::State<DbState>,
+    event_tx: &broadcast::Sender<AgentEvent>,
+    source: &(dyn AgentSource + Send + Sync),
+    session_id: &str,
+    project_dir: &str,
+    tool_use_id: &str,
+    result_payload: Option<&str>,
+) {
+    let Some(completed) = resolve_tool_call(
+        &db.0,
+        &watcher.tool_calls,
+        source.source_agent(),
+        session_id,
+        project_dir,
+        tool_use_id,
+        result_payload,
+    ) else {
+        return;
     };
 
-    // Extract useful info based on message type
-    let msg_type = entry["type"].as_str().unwrap_or("unknown");
-
-    match msg_type {
-        "user" => {
-            // User messages can have: text, image+text, or tool_result
-            let content = entry["message"]["content"].as_array();
-            if let Some(arr) = content {
-                // Look for text in any content item
-                for item in arr {
-                    if item["type"].as_str() == Some("text") {
-                        let text = item["text"].as_str()
-                            .unwrap_or("")
-                            .chars()
-                            .take(500)
-                            .collect::<String>();
-                        if !text.is_empty() {
-                            let payload = serde_json::json!({
-                                "messageType": "user",
-                                "preview": text
-                            });
-                            return (Some("UserMessage".to_string()), Some(payload.to_string()));
-                        }
-                    }
-                    if item["type"].as_str() == Some("tool_result") {
-                        let tool_use_id = item["tool_use_id"].as_str().unwrap_or("unknown");
-                        let is_error = item["is_error"].as_bool().unwrap_or(false);
-
-                        // Extract content preview
-                        let content_preview = if let Some(content) = item["content"].as_str() {
-                            content.chars().take(300).collect::<String>()
-                        } else if let Some(arr) = item["content"].as_array() {
-                            // Content can be array of text blocks
-                            arr.iter()
-                                .filter_map(|c| c["text"].as_str())
-                                .collect::<Vec<_>>()
-                                .join("\n")
-                                .chars()
-                                .take(300)
-                                .collect::<String>()
-                        } else {
-                            String::new()
-                        };
-
-                        let payload = serde_json::json!({
-                            "messageType": "tool_result",
-                            "toolUseId": tool_use_id,
-                            "isError": is_error,
-                            "preview": content_preview
-                        });
-                        return (Some("ToolResult".to_string()), Some(payload.to_string()));
-                    }
-                    if item["type"].as_str() == Some("image") {
-                        let payload = serde_json::json!({
-                            "messageType": "image",
-                            "preview": "ðŸ“· Image uploaded"
-                        });
-                        return (Some("Image".to_string()), Some(payload.to_string()));
-                    }
-                }
-            }
-            let payload = serde_json::json!({
-                "messageType": "user",
-                "preview": ""
-            });
-            (Some("UserMessage".to_string()), Some(payload.to_string()))
-        }
-        "assistant" => {
-            // Check if it's a tool use, text response, or thinking
-            let content = entry["message"]["content"].as_array();
-            if let Some(arr) = content {
-                for item in arr {
-                    if item["type"].as_str() == Some("tool_use") {
-                        let tool = item["name"].as_str().unwrap_or("unknown");
-                        let tool_input = &item["input"];
-
-                        // Extract common input fields based on tool type
-                        let mut payload = serde_json::json!({
-                            "messageType": "tool_use",
-                            "tool": tool
-                        });
-
-                        // Add tool-specific input details
-                        match tool {
-                            "Bash" => {
-                                if let Some(cmd) = tool_input["command"].as_str() {
-                                    payload["command"] = serde_json::json!(cmd.chars().take(500).collect::<String>());
-                                }
-                                if let Some(desc) = tool_input["description"].as_str() {
-                                    payload["description"] = serde_json::json!(desc);
-                                }
-                            }
-                            "Edit" => {
-                                if let Some(fp) = tool_input["file_path"].as_str() {
-                                    payload["filePath"] = serde_json::json!(fp);
-                                }
-                                if let Some(old) = tool_input["old_string"].as_str() {
-                                    payload["oldString"] = serde_json::json!(old.chars().take(200).collect::<String>());
-                                }
-                                if let Some(new) = tool_input["new_string"].as_str() {
-                                    payload["newString"] = serde_json::json!(new.chars().take(200).collect::<String>());
-                                }
-                            }
-                            "Write" => {
-                                if let Some(fp) = tool_input["file_path"].as_str() {
-                                    payload["filePath"] = serde_json::json!(fp);
-                                }
-                                if let Some(content) = tool_input["content"].as_str() {
-                                    payload["contentPreview"] = serde_json::json!(content.chars().take(200).collect::<String>());
-                                }
-                            }
-                            "Read" => {
-                                if let Some(fp) = tool_input["file_path"].as_str() {
-                                    payload["filePath"] = serde_json::json!(fp);
-                                }
-                                if let Some(offset) = tool_input["offset"].as_i64() {
-                                    payload["offset"] = serde_json::json!(offset);
-                                }
-                                if let Some(limit) = tool_input["limit"].as_i64() {
-                                    payload["limit"] = serde_json::json!(limit);
-                                }
-                            }
-                            "Grep" => {
-                                if let Some(pattern) = tool_input["pattern"].as_str() {
-                                    payload["pattern"] = serde_json::json!(pattern);
-                                }
-                                if let Some(path) = tool_input["path"].as_str() {
-                                    payload["path"] = serde_json::json!(path);
-                                }
-                            }
-                            "Glob" => {
-                                if let Some(pattern) = tool_input["pattern"].as_str() {
-                                    payload["pattern"] = serde_json::json!(pattern);
-                                }
-                                if let Some(path) = tool_input["path"].as_str() {
-                                    payload["path"] = serde_json::json!(path);
-                                }
-                            }
-                            "Task" => {
-                                if let Some(desc) = tool_input["description"].as_str() {
-                                    payload["taskDescription"] = serde_json::json!(desc);
-                                }
-                                if let Some(agent) = tool_input["subagent_type"].as_str() {
-                                    payload["subagentType"] = serde_json::json!(agent);
-                                }
-                            }
-                            _ => {
-                                // For other tools, include a preview of the input
-                                let input_str = tool_input.to_string();
-                                if input_str.len() > 2 { // More than just "{}"
-                                    payload["inputPreview"] = serde_json::json!(input_str.chars().take(300).collect::<String>());
-                                }
-                            }
-                        }
-
-                        return (Some(tool.to_string()), Some(payload.to_string()));
-                    }
-                    if item["type"].as_str() == Some("text") {
-                        let text = item["text"].as_str()
-                            .unwrap_or("")
-                            .chars()
-                            .take(500)
-                            .collect::<String>();
-                        let payload = serde_json::json!({
-                            "messageType": "assistant",
-                            "preview": text
-                        });
-                        return (Some("Response".to_string()), Some(payload.to_string()));
-                    }
-                    if item["type"].as_str() == Some("thinking") {
-                        let text = item["thinking"].as_str()
-                            .unwrap_or("")
-                            .chars()
-                            .take(500)
-                            .collect::<String>();
-                        let payload = serde_json::json!({
-                            "messageType": "thinking",
-                            "preview": text
-                        });
-                        return (Some("Thinking".to_string()), Some(payload.to_string()));
-                    }
-                }
-            }
-            (Some("Assistant".to_string()), None)
-        }
-        "result" => {
-            // Standalone result entry (different format)
-            let is_error = entry["is_error"].as_bool().unwrap_or(false);
-            let tool_use_id = entry["tool_use_id"].as_str().unwrap_or("unknown");
-
-            // Extract content preview
-            let content_preview = if let Some(content) = entry["content"].as_str() {
-                content.chars().take(300).collect::<String>()
-            } else if let Some(arr) = entry["content"].as_array() {
-                arr.iter()
-                    .filter_map(|c| c["text"].as_str())
-                    .collect::<Vec<_>>()
-                    .join("\n")
-                    .chars()
-                    .take(300)
-                    .collect::<String>()
-            } else if let Some(output) = entry["output"].as_str() {
-                // Some results use "output" field
-                output.chars().take(300).collect::<String>()
-            } else {
-                String::new()
-            };
-
-            let payload = serde_json::json!({
-                "messageType": "tool_result",
-                "toolUseId": tool_use_id,
-                "isError": is_error,
-                "preview": content_preview
-            });
-            (Some("ToolResult".to_string()), Some(payload.to_string()))
-        }
-        _ => (None, None),
-    }
+    let _ = db.0.insert_event(&completed);
+    let _ = event_tx.send(completed);
 }
 
-/// Get the active feature ID (project_dir:index) from feature_list.json
+/// Get the active feature ID (project_dir:index) from the project's feature
+/// manifest, whichever of `feature_list.{json,toml,yaml,yml}` is present.
 fn get_active_feature_id(project_dir: &str) -> Option<String> {
-    let feature_path = PathBuf::from(project_dir).join("feature_list.json");
+    let feature_path = crate::feature_manifest::find(Path::new(project_dir))?;
     let content = std::fs::read_to_string(&feature_path).ok()?;
-    let features: Vec<serde_json::Value> = serde_json::from_str(&content).ok()?;
+    let features = crate::feature_manifest::parse(&feature_path, &content).ok()?;
 
     for (index, feature) in features.iter().enumerate() {
         if feature["inProgress"].as_bool().unwrap_or(false) {
@@ -386,43 +399,26 @@ fn get_active_feature_id(project_dir: &str) -> Option<String> {
     None
 }
 
-fn handle_feature_list_change(
-    app: &tauri::AppHandle,
-    event_tx: &broadcast::Sender<AgentEvent>,
-    path: &Path,
-) {
-    let project_dir = path
-        .parent()
-        .map(|p| p.to_string_lossy().to_string())
-        .unwrap_or_default();
-
-    let content = match std::fs::read_to_string(path) {
-        Ok(c) => c,
-        Err(e) => {
-            tracing::error!("Failed to read feature_list.json: {}", e);
-            return;
-        }
-    };
-
-    let features: Vec<serde_json::Value> = match serde_json::from_str(&content) {
-        Ok(f) => f,
-        Err(e) => {
-            tracing::error!("Failed to parse feature_list.json: {}", e);
-            return;
-        }
-    };
-
-    let db: tauri::State<DbState> = app.state();
-
-    // Get old features to detect changes
-    let old_features = db.0.get_features(Some(&project_dir)).unwrap_or_default();
+/// Parse a feature manifest snapshot, sync it into `db`, and return the
+/// features that newly started passing (i.e. weren't already `passes` under
+/// the same description). Factored out of `handle_feature_list_change` so
+/// `replay::run_workload` can drive the same diff-and-sync logic without the
+/// watcher's Tauri-specific notification/emit calls.
+pub(crate) fn sync_feature_list(
+    db: &Database,
+    project_dir: &str,
+    manifest_path: &Path,
+    content: &str,
+) -> Result<Vec<Feature>, Box<dyn std::error::Error>> {
+    let features = crate::feature_manifest::parse(manifest_path, content)?;
+
+    let old_features = db.get_features(Some(project_dir)).unwrap_or_default();
     let old_completed: HashSet<String> = old_features
         .iter()
         .filter(|f| f.passes)
         .map(|f| f.description.clone())
         .collect();
 
-    // Parse new features
     let parsed_features: Vec<Feature> = features
         .iter()
         .enumerate()
@@ -437,7 +433,7 @@ fn handle_feature_list_change(
 
             Feature {
                 id: format!("{}:{}", project_dir, i),
-                project_dir: project_dir.clone(),
+                project_dir: project_dir.to_string(),
                 description: f["description"].as_str().unwrap_or("").to_string(),
                 category: f["category"].as_str().unwrap_or("functional").to_string(),
                 passes: f["passes"].as_bool().unwrap_or(false),
@@ -445,44 +441,79 @@ fn handle_feature_list_change(
                 agent: f["agent"].as_str().map(String::from),
                 steps,
                 updated_at: chrono::Utc::now().to_rfc3339(),
+                ..Default::default()
             }
         })
         .collect();
 
-    // Detect newly completed features
-    for feature in &parsed_features {
-        if feature.passes && !old_completed.contains(&feature.description) {
-            // New completion!
-            let event = AgentEvent {
-                id: None,
-                event_type: "FeatureCompleted".to_string(),
-                source_agent: feature
-                    .agent
-                    .clone()
-                    .unwrap_or_else(|| "unknown".to_string()),
-                session_id: "file-watch".to_string(),
-                project_dir: project_dir.clone(),
-                tool_name: Some(feature.description.clone()),
-                payload: Some(
-                    serde_json::json!({
-                        "category": feature.category
-                    })
-                    .to_string(),
-                ),
-                feature_id: Some(feature.id.clone()),
-                created_at: chrono::Utc::now().to_rfc3339(),
-            };
-
-            let _ = db.0.insert_event(&event);
-            let _ = event_tx.send(event);
-
-            // Send desktop notification
-            send_notification(app, "âœ… Feature Completed", &feature.description);
+    let newly_completed: Vec<Feature> = parsed_features
+        .iter()
+        .filter(|f| f.passes && !old_completed.contains(&f.description))
+        .cloned()
+        .collect();
+
+    let _ = db.sync_features(project_dir, parsed_features);
+
+    Ok(newly_completed)
+}
+
+fn handle_feature_list_change(
+    app: &tauri::AppHandle,
+    event_tx: &broadcast::Sender<AgentEvent>,
+    path: &Path,
+) {
+    let project_dir = path
+        .parent()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Failed to read feature manifest {:?}: {}", path, e);
+            return;
         }
-    }
+    };
 
-    // Sync all features to database
-    let _ = db.0.sync_features(&project_dir, parsed_features);
+    let db: tauri::State<DbState> = app.state();
+
+    let newly_completed = match sync_feature_list(&db.0, &project_dir, path, &content) {
+        Ok(features) => features,
+        Err(e) => {
+            tracing::error!("Failed to parse feature manifest {:?}: {}", path, e);
+            return;
+        }
+    };
+
+    // Emit an event and a notification for each feature that newly started
+    // passing.
+    for feature in &newly_completed {
+        let event = AgentEvent {
+            id: None,
+            event_type: "FeatureCompleted".to_string(),
+            source_agent: feature
+                .agent
+                .clone()
+                .unwrap_or_else(|| "unknown".to_string()),
+            session_id: "file-watch".to_string(),
+            project_dir: project_dir.clone(),
+            tool_name: Some(feature.description.clone()),
+            payload: Some(
+                serde_json::json!({
+                    "category": feature.category
+                })
+                .to_string(),
+            ),
+            feature_id: Some(feature.id.clone()),
+            tool_use_id: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+        };
+
+        let _ = db.0.insert_event(&event);
+        let _ = event_tx.send(event);
+
+        send_notification(app, "âœ… Feature Completed", &feature.description);
+    }
 
     // Emit refresh event to frontend
     let _ = app.emit("features-updated", &project_dir);